@@ -0,0 +1,154 @@
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+// Window the user was working in, for context in prompts
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveWindow {
+    pub title: String,
+    pub process_name: String,
+}
+
+// Foreground window captured when a global shortcut fired, before Aura took focus
+#[derive(Default)]
+pub struct LastActiveWindow(Mutex<Option<ActiveWindow>>);
+
+#[cfg(target_os = "windows")]
+fn foreground_window() -> Option<ActiveWindow> {
+    use sysinfo::{Pid, ProcessRefreshKind, System};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId,
+    };
+
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd == 0 {
+        return None;
+    }
+    let mut pid = 0;
+    unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+    if pid == 0 || pid == std::process::id() {
+        return None;
+    }
+
+    let len = unsafe { GetWindowTextLengthW(hwnd) }.max(0) as usize;
+    let mut buffer = vec![0u16; len + 1];
+    let copied = unsafe { GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32) };
+    let title = String::from_utf16_lossy(&buffer[..copied.max(0) as usize]);
+
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_process_specifics(pid, ProcessRefreshKind::new());
+    let process_name = system.process(pid)?.name().to_string();
+    Some(ActiveWindow {
+        title,
+        process_name,
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn foreground_window() -> Option<ActiveWindow> {
+    use cocoa::base::{id, nil};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let frontmost: id = msg_send![workspace, frontmostApplication];
+        if frontmost == nil {
+            return None;
+        }
+        let pid: i32 = msg_send![frontmost, processIdentifier];
+        if pid as u32 == std::process::id() {
+            return None;
+        }
+        let process_name = ns_string(msg_send![frontmost, localizedName])?;
+        // Window titles need the Screen Recording permission; without it only the app is known
+        let title = window_title(pid).unwrap_or_default();
+        Some(ActiveWindow {
+            title,
+            process_name,
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn ns_string(string: cocoa::base::id) -> Option<String> {
+    use objc::{msg_send, sel, sel_impl};
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+
+    if string == cocoa::base::nil {
+        return None;
+    }
+    let utf8: *const c_char = msg_send![string, UTF8String];
+    if utf8.is_null() {
+        return None;
+    }
+    Some(CStr::from_ptr(utf8).to_string_lossy().into_owned())
+}
+
+// Title of the frontmost normal-layer window owned by pid
+#[cfg(target_os = "macos")]
+unsafe fn window_title(pid: i32) -> Option<String> {
+    use cocoa::base::{id, nil};
+    use objc::{msg_send, sel, sel_impl};
+
+    const ON_SCREEN_ONLY: u32 = 1 << 0;
+    const EXCLUDE_DESKTOP_ELEMENTS: u32 = 1 << 4;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        static kCGWindowOwnerPID: id;
+        static kCGWindowLayer: id;
+        static kCGWindowName: id;
+        fn CGWindowListCopyWindowInfo(option: u32, relative_to_window: u32) -> id;
+    }
+
+    // Front to back, so the first match is the window the user was looking at
+    let windows = CGWindowListCopyWindowInfo(ON_SCREEN_ONLY | EXCLUDE_DESKTOP_ELEMENTS, 0);
+    if windows == nil {
+        return None;
+    }
+    let count: usize = msg_send![windows, count];
+    let mut title = None;
+    for index in 0..count {
+        let info: id = msg_send![windows, objectAtIndex: index];
+        let owner: id = msg_send![info, objectForKey: kCGWindowOwnerPID];
+        let layer: id = msg_send![info, objectForKey: kCGWindowLayer];
+        if owner == nil || layer == nil {
+            continue;
+        }
+        let owner: i32 = msg_send![owner, intValue];
+        let layer: i32 = msg_send![layer, intValue];
+        if owner == pid && layer == 0 {
+            title = ns_string(msg_send![info, objectForKey: kCGWindowName]);
+            break;
+        }
+    }
+    let _: () = msg_send![windows, release];
+    title
+}
+
+// Other platforms offer no portable way to read the foreground window
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn foreground_window() -> Option<ActiveWindow> {
+    None
+}
+
+// Note the foreground window; called from the global-shortcut handlers, before Aura can take
+// focus. Aura itself in front keeps the window from before
+pub fn capture(app: &AppHandle) {
+    if let Some(active) = foreground_window() {
+        if let Some(last) = app.try_state::<LastActiveWindow>() {
+            *last.0.lock().unwrap() = Some(active);
+        }
+    }
+}
+
+// Title and process of the window the user was in when Aura was summoned; None when it could
+// not be read on this platform or without the needed permission
+#[tauri::command]
+pub fn get_active_window(last: State<'_, LastActiveWindow>) -> Option<ActiveWindow> {
+    last.0.lock().unwrap().clone()
+}
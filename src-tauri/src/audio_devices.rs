@@ -0,0 +1,91 @@
+use cpal::traits::{DeviceTrait, HostTrait};
+use cpal::Device;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::settings;
+
+// Audio devices offered by the system, by name
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioDevices {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub default_input: Option<String>,
+    pub default_output: Option<String>,
+}
+
+// Sent when the chosen microphone is gone and the system default is used instead
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceChanged {
+    pub requested: String,
+    pub device: Option<String>,
+}
+
+fn names<I: Iterator<Item = Device>>(devices: I) -> Vec<String> {
+    devices.filter_map(|device| device.name().ok()).collect()
+}
+
+// Microphone to record from: the one picked in settings, else the system default
+pub fn input_device(app: &AppHandle) -> Result<Device, String> {
+    let host = cpal::default_host();
+    let requested = settings::current(app).input_device;
+    if let Some(requested) = requested {
+        let found = host
+            .input_devices()
+            .map_err(|err| format!("Failed to list microphones: {}", err))?
+            .find(|device| device.name().map_or(false, |name| name == requested));
+        if let Some(device) = found {
+            return Ok(device);
+        }
+
+        // Unplugged since it was picked; the preference is kept for when it comes back
+        let device = host.default_input_device();
+        let payload = DeviceChanged {
+            requested,
+            device: device.as_ref().and_then(|device| device.name().ok()),
+        };
+        tracing::warn!(
+            "Microphone '{}' not found, using the default",
+            payload.requested
+        );
+        if let Some(window) = crate::main_window(app) {
+            window
+                .emit("device-changed", payload)
+                .unwrap_or_else(|err| tracing::error!("Failed to emit device change: {}", err));
+        }
+        return device.ok_or_else(|| "No microphone found".to_string());
+    }
+    host.default_input_device()
+        .ok_or_else(|| "No microphone found".to_string())
+}
+
+// Input and output devices for the microphone picker
+#[tauri::command]
+pub fn list_audio_devices() -> Result<AudioDevices, String> {
+    let host = cpal::default_host();
+    let inputs = host
+        .input_devices()
+        .map_err(|err| format!("Failed to list microphones: {}", err))?;
+    let outputs = host
+        .output_devices()
+        .map_err(|err| format!("Failed to list speakers: {}", err))?;
+    Ok(AudioDevices {
+        inputs: names(inputs),
+        outputs: names(outputs),
+        default_input: host
+            .default_input_device()
+            .and_then(|device| device.name().ok()),
+        default_output: host
+            .default_output_device()
+            .and_then(|device| device.name().ok()),
+    })
+}
+
+// Record from the named microphone; an empty name goes back to the system default
+#[tauri::command]
+pub fn set_input_device(app: AppHandle, name: String) -> Result<(), String> {
+    let device = if name.is_empty() { None } else { Some(name) };
+    settings::update(&app, |settings| settings.input_device = device)
+}
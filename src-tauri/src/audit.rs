@@ -0,0 +1,57 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::file_metadata;
+
+// File under app_log_dir() recording every file Aura removed, one JSON object per line; unlike
+// the rotating logs it is never pruned
+const AUDIT_FILE: &str = "audit.log";
+
+#[derive(Debug, Serialize)]
+struct AuditEntry<'a> {
+    time: Option<String>,
+    action: &'a str,
+    path: String,
+    error: Option<&'a str>,
+}
+
+fn append(app: &AppHandle, line: &str) -> Result<(), String> {
+    let dir = app
+        .path_resolver()
+        .app_log_dir()
+        .ok_or("Log directory not available")?;
+    fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(AUDIT_FILE))
+        .map_err(|err| err.to_string())?;
+    writeln!(file, "{}", line).map_err(|err| err.to_string())
+}
+
+// Record a destructive action on a path and whether it succeeded
+pub fn record<T>(app: &AppHandle, action: &str, path: &Path, result: &Result<T, String>) {
+    let entry = AuditEntry {
+        time: file_metadata::iso8601(SystemTime::now()),
+        action,
+        path: path.to_string_lossy().to_string(),
+        error: result.as_ref().err().map(String::as_str),
+    };
+    match &entry.error {
+        None => tracing::info!("audit: {} '{}'", action, entry.path),
+        Some(err) => tracing::warn!("audit: {} '{}' failed: {}", action, entry.path, err),
+    }
+    let line = match serde_json::to_string(&entry) {
+        Ok(line) => line,
+        Err(err) => {
+            tracing::error!("Failed to serialize audit entry: {}", err);
+            return;
+        }
+    };
+    append(app, &line).unwrap_or_else(|err| tracing::error!("Failed to write audit log: {}", err));
+}
@@ -0,0 +1,41 @@
+use tauri::AppHandle;
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::{settings, tray_state};
+
+// Tray menu item mirroring the launch-at-login registration
+pub const TRAY_ITEM_ID: &str = "autostart";
+
+// Whether Aura is registered to launch at login
+pub fn is_enabled(app: &AppHandle) -> bool {
+    app.autolaunch().is_enabled().unwrap_or_else(|err| {
+        tracing::error!("Failed to read autostart state: {}", err);
+        false
+    })
+}
+
+// Register or unregister launch at login, persist it and update the tray checkmark
+pub fn apply(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let autolaunch = app.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|err| err.to_string())?;
+    settings::update(app, |settings| settings.launch_at_login = enabled)?;
+
+    tray_state::set_checked(app, TRAY_ITEM_ID, enabled)
+}
+
+// Enable or disable launching Aura at login
+#[tauri::command]
+pub fn set_autostart(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply(&app, enabled)
+}
+
+// Check whether Aura launches at login
+#[tauri::command]
+pub fn get_autostart(app: AppHandle) -> bool {
+    is_enabled(&app)
+}
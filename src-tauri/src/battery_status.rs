@@ -0,0 +1,106 @@
+use std::thread;
+use std::time::Duration;
+
+use battery::units::ratio::percent;
+use battery::units::time::second;
+use battery::{Battery, State};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::pause;
+
+// How often the watcher checks whether the charging state flipped
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+// Charging state reported to the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChargeState {
+    Charging,
+    Discharging,
+    Full,
+    Empty,
+    Unknown,
+}
+
+impl From<State> for ChargeState {
+    fn from(state: State) -> Self {
+        match state {
+            State::Charging => ChargeState::Charging,
+            State::Discharging => ChargeState::Discharging,
+            State::Full => ChargeState::Full,
+            State::Empty => ChargeState::Empty,
+            _ => ChargeState::Unknown,
+        }
+    }
+}
+
+// Charge level of the main battery
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryStatus {
+    pub percentage: f32,
+    pub state: ChargeState,
+    pub seconds_to_empty: Option<u64>,
+}
+
+impl From<&Battery> for BatteryStatus {
+    fn from(battery: &Battery) -> Self {
+        BatteryStatus {
+            percentage: battery.state_of_charge().get::<percent>(),
+            state: battery.state().into(),
+            seconds_to_empty: battery
+                .time_to_empty()
+                .map(|time| time.get::<second>().round() as u64),
+        }
+    }
+}
+
+// Status of the first battery, or None on machines without one
+fn read() -> Option<BatteryStatus> {
+    let manager = match battery::Manager::new() {
+        Ok(manager) => manager,
+        Err(err) => {
+            tracing::warn!("Battery information unavailable: {}", err);
+            return None;
+        }
+    };
+    let battery = manager.batteries().ok()?.find_map(Result::ok)?;
+    Some(BatteryStatus::from(&battery))
+}
+
+// Emit battery-changed whenever the charging state flips, so the frontend need not poll
+pub fn watch_battery(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let mut last_state = match read() {
+            Some(status) => status.state,
+            None => return,
+        };
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if pause::is_paused(&app) {
+                continue;
+            }
+            let status = match read() {
+                Some(status) => status,
+                None => continue,
+            };
+            if status.state == last_state {
+                continue;
+            }
+            last_state = status.state;
+            if let Some(window) = crate::main_window(&app) {
+                window
+                    .emit("battery-changed", status)
+                    .unwrap_or_else(|err| tracing::error!("Failed to emit battery state: {}", err));
+            }
+        }
+    });
+}
+
+// Current battery charge, or None on desktops without a battery
+#[tauri::command]
+pub fn get_battery_status() -> Option<BatteryStatus> {
+    read()
+}
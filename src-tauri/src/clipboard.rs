@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+use std::io::Cursor;
+
+use arboard::{Clipboard, ImageData};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Serialize;
+use xcap::image::{self, ImageFormat, RgbaImage};
+
+// Errors returned to the frontend by the clipboard commands
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum ClipboardError {
+    NotText(String),
+    NoImage(String),
+    InvalidImage(String),
+    Unavailable(String),
+}
+
+impl From<arboard::Error> for ClipboardError {
+    fn from(err: arboard::Error) -> Self {
+        match err {
+            arboard::Error::ContentNotAvailable => {
+                ClipboardError::NotText("Clipboard does not contain text".to_string())
+            }
+            err => ClipboardError::Unavailable(err.to_string()),
+        }
+    }
+}
+
+// Read text from the system clipboard
+#[tauri::command]
+pub fn read_clipboard() -> Result<String, ClipboardError> {
+    Ok(Clipboard::new()?.get_text()?)
+}
+
+// Write text to the system clipboard
+#[tauri::command]
+pub fn write_clipboard(text: String) -> Result<(), ClipboardError> {
+    Ok(Clipboard::new()?.set_text(text)?)
+}
+
+// Read an image from the system clipboard, e.g. a copied screenshot, as a base64-encoded PNG
+#[tauri::command]
+pub async fn read_clipboard_image() -> Result<String, ClipboardError> {
+    let image = Clipboard::new()?.get_image().map_err(|err| match err {
+        arboard::Error::ContentNotAvailable => {
+            ClipboardError::NoImage("Clipboard does not contain an image".to_string())
+        }
+        err => err.into(),
+    })?;
+    let rgba = RgbaImage::from_raw(
+        image.width as u32,
+        image.height as u32,
+        image.bytes.into_owned(),
+    )
+    .ok_or_else(|| ClipboardError::InvalidImage("Clipboard image is incomplete".to_string()))?;
+
+    let mut png = Vec::new();
+    rgba.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|err| {
+            ClipboardError::InvalidImage(format!("Failed to encode clipboard image: {}", err))
+        })?;
+    Ok(STANDARD.encode(png))
+}
+
+// Put a base64-encoded PNG, optionally as a data URL, on the system clipboard
+#[tauri::command]
+pub async fn write_clipboard_image(base64_png: String) -> Result<(), ClipboardError> {
+    let encoded = base64_png.trim();
+    let encoded = encoded
+        .strip_prefix("data:image/png;base64,")
+        .unwrap_or(encoded);
+    let png = STANDARD
+        .decode(encoded)
+        .map_err(|err| ClipboardError::InvalidImage(format!("Image is not base64: {}", err)))?;
+    let rgba = image::load_from_memory_with_format(&png, ImageFormat::Png)
+        .map_err(|err| ClipboardError::InvalidImage(format!("Image is not a PNG: {}", err)))?
+        .to_rgba8();
+
+    let (width, height) = rgba.dimensions();
+    Ok(Clipboard::new()?.set_image(ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: Cow::Owned(rgba.into_raw()),
+    })?)
+}
@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State, Window};
+
+use crate::settings;
+use crate::shutdown;
+use crate::tray_presence;
+
+// What the main window's close button does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CloseBehavior {
+    Hide,
+    Quit,
+    Ask,
+}
+
+// The user's answer to a confirm-close prompt
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CloseAction {
+    Hide,
+    Quit,
+    Cancel,
+}
+
+// Set while the frontend is asking the user whether to hide or quit
+#[derive(Default)]
+pub struct PendingClose(AtomicBool);
+
+fn hide(window: &Window) {
+    window
+        .hide()
+        .unwrap_or_else(|err| tracing::error!("Failed to hide window: {}", err));
+}
+
+// Handle the close button according to the configured behavior; the window itself is
+// never destroyed because the tray and shortcuts reuse it. Without a tray a hidden window could
+// not be found again, so closing always quits
+pub fn on_close_requested(window: &Window) {
+    let app = window.app_handle();
+    if !tray_presence::is_available(&app) {
+        return shutdown::quit(&app);
+    }
+    match settings::current(&app).close_behavior {
+        CloseBehavior::Hide => hide(window),
+        CloseBehavior::Quit => shutdown::quit(&app),
+        CloseBehavior::Ask => {
+            app.state::<PendingClose>().0.store(true, Ordering::SeqCst);
+            window
+                .emit("confirm-close", ())
+                .unwrap_or_else(|err| tracing::error!("Failed to emit confirm-close: {}", err));
+        }
+    }
+}
+
+// Carry out the choice made in the confirm-close prompt
+#[tauri::command]
+pub fn confirm_close(
+    app: AppHandle,
+    pending: State<PendingClose>,
+    action: CloseAction,
+) -> Result<(), String> {
+    if !pending.0.swap(false, Ordering::SeqCst) {
+        return Err("No close is waiting for confirmation".to_string());
+    }
+
+    match action {
+        CloseAction::Hide => {
+            let window = crate::main_window(&app).ok_or("Main window not found")?;
+            window.hide().map_err(|err| err.to_string())
+        }
+        CloseAction::Quit => {
+            shutdown::quit(&app);
+            Ok(())
+        }
+        CloseAction::Cancel => Ok(()),
+    }
+}
+
+// Change what the close button does
+#[tauri::command]
+pub fn set_close_behavior(app: AppHandle, behavior: CloseBehavior) -> Result<(), String> {
+    settings::update(&app, |settings| settings.close_behavior = behavior)
+}
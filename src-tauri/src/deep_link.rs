@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Url};
+
+// URL scheme Aura handles, e.g. aura://ask?q=weather; keep in sync with Info.plist and the README
+pub const SCHEME: &str = "aura";
+
+// Bundle identifier from tauri.conf.json, naming the socket links are forwarded over
+const IDENTIFIER: &str = "com.aura.desktop-assistant";
+
+// Parsed deep link sent to the main window
+#[derive(Debug, Clone, Serialize)]
+pub struct DeepLinkPayload {
+    pub path: String,
+    pub query: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct DeepLinkState {
+    ready: bool,
+    pending: Vec<DeepLinkPayload>,
+}
+
+// Links that arrived before the frontend was listening, replayed by deep_link_ready
+#[derive(Default)]
+pub struct DeepLinks(Mutex<DeepLinkState>);
+
+fn launch_url() -> Option<String> {
+    std::env::args()
+        .nth(1)
+        .filter(|arg| arg.starts_with(&format!("{}:", SCHEME)))
+}
+
+// Run before the builder: a launch for a link hands it to the running instance and exits
+pub fn prepare() {
+    if launch_url().is_some() {
+        tauri_plugin_deep_link::prepare(IDENTIFIER);
+    } else if let Err(err) = tauri_plugin_deep_link::set_identifier(IDENTIFIER) {
+        tracing::error!("Failed to set deep link identifier: {}", err);
+    }
+}
+
+// Split aura://ask?q=hi into the path "ask" and its query parameters
+fn parse(url: &str) -> Option<DeepLinkPayload> {
+    let url = Url::parse(url).ok()?;
+    if url.scheme() != SCHEME {
+        return None;
+    }
+    let path = format!("{}{}", url.host_str().unwrap_or_default(), url.path());
+    Some(DeepLinkPayload {
+        path: path.trim_matches('/').to_string(),
+        query: url.query_pairs().into_owned().collect(),
+    })
+}
+
+fn handle(app: &AppHandle, url: &str) {
+    let payload = match parse(url) {
+        Some(payload) => payload,
+        None => {
+            tracing::warn!("Ignoring deep link '{}'", url);
+            return;
+        }
+    };
+
+    if let Some(window) = crate::main_window(app) {
+        crate::show_and_focus(&window)
+            .unwrap_or_else(|err| tracing::error!("Failed to show window: {}", err));
+    }
+
+    let state = app.state::<DeepLinks>();
+    let mut state = state.0.lock().unwrap();
+    if state.ready {
+        emit(app, payload);
+    } else {
+        state.pending.push(payload);
+    }
+}
+
+fn emit(app: &AppHandle, payload: DeepLinkPayload) {
+    if let Some(window) = crate::main_window(app) {
+        window
+            .emit("deep-link", payload)
+            .unwrap_or_else(|err| tracing::error!("Failed to emit deep link: {}", err));
+    }
+}
+
+// Register the scheme with the OS and queue the link this instance was launched with
+pub fn register(app: &AppHandle) {
+    let app_handle = app.clone();
+    if let Err(err) = tauri_plugin_deep_link::register(SCHEME, move |url| handle(&app_handle, &url))
+    {
+        tracing::error!("Failed to register the {} URL scheme: {}", SCHEME, err);
+    }
+
+    // On Windows and Linux a cold start receives the link as its first argument
+    if let Some(url) = launch_url() {
+        handle(app, &url);
+    }
+}
+
+// Called by the frontend once it listens for deep-link, replaying links received before that
+#[tauri::command]
+pub fn deep_link_ready(app: AppHandle) {
+    let state = app.state::<DeepLinks>();
+    let mut state = state.0.lock().unwrap();
+    state.ready = true;
+    for payload in state.pending.drain(..) {
+        emit(&app, payload);
+    }
+}
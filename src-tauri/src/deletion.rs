@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{audit, files};
+
+// How long a confirmation token from request_destructive_confirmation stays valid
+const CONFIRMATION_TTL: Duration = Duration::from_secs(60);
+
+// What happened to one path of a batch; error is set when it was left in place
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteResult {
+    pub path: String,
+    pub deleted: bool,
+    pub error: Option<String>,
+}
+
+struct PendingConfirmation {
+    paths: Vec<String>,
+    issued: Instant,
+}
+
+// Tokens handed out for permanent deletion, each valid once for exactly the paths it was
+// requested for
+#[derive(Default)]
+pub struct Confirmations {
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+}
+
+// 128 bits from the OS random number generator
+fn new_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|err| format!("Failed to generate a confirmation token: {}", err))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+fn remove(path: &Path) -> Result<(), String> {
+    let metadata = fs::symlink_metadata(path).map_err(|err| err.to_string())?;
+    let result = if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        // Windows directory links are removed as directories
+        fs::remove_file(path).or_else(|err| fs::remove_dir(path).map_err(|_| err))
+    };
+    result.map_err(|err| err.to_string())
+}
+
+// Apply action to every path in the sandbox, carrying on past the ones that fail. Paths the
+// sandbox rejects are audited as failures too
+fn delete_each(
+    app: &AppHandle,
+    paths: Vec<String>,
+    action: &str,
+    delete: impl Fn(&Path) -> Result<(), String>,
+) -> Vec<DeleteResult> {
+    paths
+        .into_iter()
+        .map(|path| {
+            let (target, result) = match files::sandboxed_entry(app, Path::new(&path)) {
+                Ok(target) => {
+                    let result = delete(&target);
+                    (target, result)
+                }
+                Err(err) => (PathBuf::from(&path), Err(err)),
+            };
+            audit::record(app, action, &target, &result);
+            DeleteResult {
+                path,
+                deleted: result.is_ok(),
+                error: result.err(),
+            }
+        })
+        .collect()
+}
+
+// Run delete_each off the async runtime's threads, since a large folder takes a while
+async fn delete_blocking(
+    app: AppHandle,
+    paths: Vec<String>,
+    action: &'static str,
+    delete: impl Fn(&Path) -> Result<(), String> + Send + 'static,
+) -> Result<Vec<DeleteResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || delete_each(&app, paths, action, delete))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+// Move files and folders to the recycle bin or trash, reporting on each path separately so
+// one locked file does not stop the rest
+#[tauri::command]
+pub async fn delete_to_trash(
+    app: AppHandle,
+    paths: Vec<String>,
+) -> Result<Vec<DeleteResult>, String> {
+    delete_blocking(app, paths, "trash", |target| {
+        trash::delete(target).map_err(|err| format!("Failed to move to the trash: {}", err))
+    })
+    .await
+}
+
+// Issue a one-time token for delete_permanently, once the user has confirmed removing paths
+#[tauri::command]
+pub fn request_destructive_confirmation(
+    confirmations: State<'_, Confirmations>,
+    paths: Vec<String>,
+) -> Result<String, String> {
+    if paths.is_empty() {
+        return Err("No paths to confirm".into());
+    }
+    let token = new_token()?;
+    let mut pending = confirmations.pending.lock().unwrap();
+    pending.retain(|_, confirmation| confirmation.issued.elapsed() < CONFIRMATION_TTL);
+    pending.insert(
+        token.clone(),
+        PendingConfirmation {
+            paths,
+            issued: Instant::now(),
+        },
+    );
+    Ok(token)
+}
+
+// Delete files and folders for good. Needs a token from request_destructive_confirmation for
+// the same paths, which is used up by the call
+#[tauri::command]
+pub async fn delete_permanently(
+    app: AppHandle,
+    confirmations: State<'_, Confirmations>,
+    paths: Vec<String>,
+    confirm_token: String,
+) -> Result<Vec<DeleteResult>, String> {
+    let confirmation = confirmations
+        .pending
+        .lock()
+        .unwrap()
+        .remove(&confirm_token)
+        .ok_or("Deletion was not confirmed")?;
+    if confirmation.issued.elapsed() >= CONFIRMATION_TTL {
+        return Err("Confirmation expired".into());
+    }
+    if confirmation.paths != paths {
+        return Err("Confirmation was given for different paths".into());
+    }
+    delete_blocking(app, paths, "delete", remove).await
+}
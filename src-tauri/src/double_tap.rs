@@ -0,0 +1,175 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use rdev::{Event, EventType};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::push_to_talk::{self, Modifier};
+use crate::settings;
+
+// Modifier tapped twice to toggle the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DoubleTapModifier {
+    Ctrl,
+    Shift,
+    Alt,
+}
+
+impl DoubleTapModifier {
+    fn modifier(self) -> Modifier {
+        match self {
+            DoubleTapModifier::Ctrl => Modifier::Control,
+            DoubleTapModifier::Shift => Modifier::Shift,
+            DoubleTapModifier::Alt => Modifier::Alt,
+        }
+    }
+}
+
+// Double-tap activation preferences, stored in the settings file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DoubleTapActivation {
+    pub enabled: bool,
+    pub modifier: DoubleTapModifier,
+    pub interval_ms: u64,
+}
+
+impl Default for DoubleTapActivation {
+    fn default() -> Self {
+        DoubleTapActivation {
+            enabled: false,
+            modifier: DoubleTapModifier::Ctrl,
+            interval_ms: 350,
+        }
+    }
+}
+
+struct Detector {
+    config: DoubleTapActivation,
+    // When the modifier went down, while it is held on its own
+    pressed_at: Option<SystemTime>,
+    // Another key was pressed while the modifier was held
+    chord: bool,
+    // When the last clean tap was released
+    last_tap: Option<SystemTime>,
+}
+
+impl Detector {
+    fn reset(&mut self) {
+        self.pressed_at = None;
+        self.chord = false;
+        self.last_tap = None;
+    }
+
+    // True when this event completes a double tap
+    fn on_event(&mut self, event: &Event) -> bool {
+        let target = self.config.modifier.modifier();
+        let interval = Duration::from_millis(self.config.interval_ms);
+        let within_interval = |since: SystemTime| {
+            event
+                .time
+                .duration_since(since)
+                .map_or(false, |elapsed| elapsed <= interval)
+        };
+
+        match event.event_type {
+            EventType::KeyPress(key) if Modifier::of(key) == Some(target) => {
+                // Key repeat sends more presses while the modifier is held
+                if self.pressed_at.is_none() {
+                    self.pressed_at = Some(event.time);
+                    self.chord = false;
+                }
+                false
+            }
+            EventType::KeyPress(_) => {
+                // Any other key makes this a chord, e.g. Ctrl+C, Ctrl+C
+                self.chord = true;
+                self.last_tap = None;
+                false
+            }
+            EventType::KeyRelease(key) if Modifier::of(key) == Some(target) => {
+                let pressed_at = match self.pressed_at.take() {
+                    Some(pressed_at) => pressed_at,
+                    None => return false,
+                };
+                // A chord or a long hold is not a tap
+                if self.chord || !within_interval(pressed_at) {
+                    self.last_tap = None;
+                    return false;
+                }
+                match self.last_tap.take() {
+                    Some(last_tap) if within_interval(last_tap) => true,
+                    _ => {
+                        self.last_tap = Some(event.time);
+                        false
+                    }
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+// Double-tap detector fed by the push-to-talk keyboard hook, which is the only hook the
+// process may install
+pub struct DoubleTap(Mutex<Detector>);
+
+impl DoubleTap {
+    pub fn new(config: DoubleTapActivation) -> Self {
+        DoubleTap(Mutex::new(Detector {
+            config,
+            pressed_at: None,
+            chord: false,
+            last_tap: None,
+        }))
+    }
+}
+
+// Toggle the main window when the configured modifier is tapped twice
+pub fn on_event(app: &AppHandle, event: &Event) {
+    let double_tap = match app.try_state::<DoubleTap>() {
+        Some(double_tap) => double_tap,
+        None => return,
+    };
+    let fired = {
+        let mut detector = double_tap.0.lock().unwrap();
+        if !detector.config.enabled {
+            return;
+        }
+        detector.on_event(event)
+    };
+    if fired {
+        crate::toggle_main_window(app);
+    }
+}
+
+// Replace the double-tap preferences and persist them
+pub fn apply(app: &AppHandle, config: DoubleTapActivation) -> Result<(), String> {
+    {
+        let double_tap = app.state::<DoubleTap>();
+        let mut detector = double_tap.0.lock().unwrap();
+        detector.config = config.clone();
+        detector.reset();
+    }
+    settings::update(app, |settings| settings.double_tap_activation = config)?;
+    // Turning double-tap on may be the first thing that needs the keyboard hook
+    push_to_talk::ensure_hook(app);
+    Ok(())
+}
+
+// Turn double-tap activation on or off and choose the modifier
+#[tauri::command]
+pub fn set_double_tap_activation(
+    app: AppHandle,
+    modifier: DoubleTapModifier,
+    enabled: bool,
+) -> Result<(), String> {
+    let config = DoubleTapActivation {
+        enabled,
+        modifier,
+        ..settings::current(&app).double_tap_activation
+    };
+    apply(&app, config)
+}
@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Url};
+
+use crate::files;
+
+// Schemes besides http(s) and file that may be handed to the OS
+const ALLOWED_SCHEMES: [&str; 1] = ["mailto"];
+
+// Resolve a local path and require it to exist inside the user's home directory
+fn check_local_path(path: &Path) -> Result<PathBuf, String> {
+    let resolved = path
+        .canonicalize()
+        .map_err(|_| format!("'{}' does not exist", path.display()))?;
+    let home = tauri::api::path::home_dir().ok_or("Home directory is unavailable")?;
+    let home = home.canonicalize().unwrap_or(home);
+    if !resolved.starts_with(&home) {
+        return Err(format!(
+            "'{}' is outside the home directory",
+            path.display()
+        ));
+    }
+    Ok(resolved)
+}
+
+// Open a URL in the default browser or a local file in its default application
+#[tauri::command]
+pub fn open_external(target: String) -> Result<(), String> {
+    // Checked first because Windows drive letters parse as URL schemes
+    let local = Path::new(&target);
+    let resolved = if local.is_absolute() {
+        check_local_path(local)?.into_os_string()
+    } else {
+        let url =
+            Url::parse(&target).map_err(|err| format!("Invalid target '{}': {}", target, err))?;
+        match url.scheme() {
+            "http" | "https" => target.clone().into(),
+            "file" => {
+                let path = url
+                    .to_file_path()
+                    .map_err(|_| format!("Invalid file URL '{}'", target))?;
+                check_local_path(&path)?.into_os_string()
+            }
+            scheme if ALLOWED_SCHEMES.contains(&scheme) => target.clone().into(),
+            scheme => return Err(format!("Opening '{}:' links is not allowed", scheme)),
+        }
+    };
+
+    open::that(&resolved).map_err(|err| format!("Failed to open '{}': {}", target, err))
+}
+
+// Resolve an existing file or directory inside the documents or app data directories; URLs go
+// through open_external instead
+fn check_sandboxed_path(app: &AppHandle, path: &str) -> Result<PathBuf, String> {
+    let local = Path::new(path);
+    if !local.is_absolute() {
+        if Url::parse(path).is_ok() {
+            return Err(format!("'{}' is a URL, not a file path", path));
+        }
+        return Err(format!("'{}' is not an absolute path", path));
+    }
+    let resolved = local
+        .canonicalize()
+        .map_err(|_| format!("'{}' does not exist", path))?;
+    if !files::writable_roots(app)
+        .iter()
+        .any(|root| resolved.starts_with(root))
+    {
+        return Err(format!(
+            "'{}' is outside the documents and app data directories",
+            path
+        ));
+    }
+    Ok(resolved)
+}
+
+// Open a file in its default application, or a directory in the file manager
+#[tauri::command]
+pub async fn open_path(app: AppHandle, path: String) -> Result<(), String> {
+    let resolved = check_sandboxed_path(&app, &path)?;
+    open::that(&resolved).map_err(|err| format!("Failed to open '{}': {}", path, err))
+}
+
+// Open a file with a named application, e.g. "Microsoft Excel" or "Preview"
+#[tauri::command]
+pub async fn open_with(app: AppHandle, path: String, application: String) -> Result<(), String> {
+    let resolved = check_sandboxed_path(&app, &path)?;
+    open::with(&resolved, &application)
+        .map_err(|err| format!("Failed to open '{}' with {}: {}", path, application, err))
+}
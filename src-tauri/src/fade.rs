@@ -0,0 +1,205 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use tauri::{Manager, Window};
+
+use crate::opacity;
+use crate::settings;
+
+// Time between opacity steps, about one frame at 60 Hz
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    In,
+    Out,
+}
+
+#[derive(Default)]
+struct FadeState {
+    generation: u64,
+    running: Option<Direction>,
+}
+
+// Fade in progress on the main window, so a new show or hide can cancel it
+#[derive(Default)]
+pub struct Fade(Mutex<FadeState>);
+
+impl Fade {
+    // Start a transition, cancelling the running one; returns its generation and whether
+    // another fade was interrupted
+    fn begin(&self, direction: Direction, animated: bool) -> (u64, bool) {
+        let mut state = self.0.lock().unwrap();
+        let interrupted = state.running.is_some();
+        state.generation += 1;
+        state.running = if animated && !interrupted {
+            Some(direction)
+        } else {
+            None
+        };
+        (state.generation, interrupted)
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        self.0.lock().unwrap().generation == generation
+    }
+
+    fn finish(&self, generation: u64) {
+        let mut state = self.0.lock().unwrap();
+        if state.generation == generation {
+            state.running = None;
+        }
+    }
+
+    fn is_fading_out(&self) -> bool {
+        self.0.lock().unwrap().running == Some(Direction::Out)
+    }
+}
+
+// Whether the OS asks apps to minimize motion
+#[cfg(target_os = "windows")]
+fn reduce_motion() -> bool {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION,
+    };
+
+    let mut animations = 1;
+    let ok = unsafe {
+        SystemParametersInfoW(
+            SPI_GETCLIENTAREAANIMATION,
+            0,
+            &mut animations as *mut i32 as *mut _,
+            0,
+        )
+    };
+    ok != 0 && animations == 0
+}
+
+// Whether the OS asks apps to minimize motion
+#[cfg(target_os = "macos")]
+fn reduce_motion() -> bool {
+    use cocoa::base::{id, BOOL, NO};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let reduce: BOOL = msg_send![workspace, accessibilityDisplayShouldReduceMotion];
+        reduce != NO
+    }
+}
+
+// Whether the OS asks apps to minimize motion
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn reduce_motion() -> bool {
+    false
+}
+
+// Fade unless the user turned animations off or set a zero duration; without a choice,
+// follow the OS preference
+fn animations_enabled(window: &Window) -> bool {
+    let settings = settings::current(&window.app_handle());
+    settings.fade_duration_ms > 0
+        && settings
+            .animations_enabled
+            .unwrap_or_else(|| !reduce_motion())
+}
+
+// Opacity the window rests at when fully shown
+fn resting_opacity(window: &Window) -> f64 {
+    opacity::clamp(settings::current(&window.app_handle()).window_opacity)
+}
+
+// Set the opacity on the main thread, where AppKit requires window changes to happen; posted
+// steps run in order with the show and hide calls around them
+fn set_opacity(window: &Window, value: f64) {
+    let target = window.clone();
+    let result = window.run_on_main_thread(move || {
+        if let Err(err) = opacity::apply(&target, value) {
+            tracing::error!("Failed to set window opacity: {:?}", err);
+        }
+    });
+    if let Err(err) = result {
+        tracing::error!("Failed to set window opacity: {}", err);
+    }
+}
+
+// Step the opacity between two values, stopping early if a newer transition started; only the
+// timing happens on the calling thread
+fn ramp(window: &Window, generation: u64, from: f64, to: f64) -> bool {
+    let fade = window.state::<Fade>();
+    let duration = Duration::from_millis(settings::current(&window.app_handle()).fade_duration_ms);
+    let frames = ((duration.as_millis() / FRAME_INTERVAL.as_millis()) as u32).max(1);
+    let frame_delay = duration / frames;
+    for frame in 1..=frames {
+        thread::sleep(frame_delay);
+        if !fade.is_current(generation) {
+            return false;
+        }
+        let progress = frame as f64 / frames as f64;
+        set_opacity(window, from + (to - from) * progress);
+    }
+    true
+}
+
+// Whether the window counts as shown, treating a window that is fading out as hidden
+pub fn is_shown(window: &Window) -> tauri::Result<bool> {
+    Ok(window.is_visible()? && !window.state::<Fade>().is_fading_out())
+}
+
+// Show and focus the window, fading it in when animations are enabled
+pub fn show(window: &Window) -> tauri::Result<()> {
+    let target = resting_opacity(window);
+    let animated = animations_enabled(window);
+    let (generation, interrupted) = window.state::<Fade>().begin(Direction::In, animated);
+
+    // Toggling mid-fade snaps to the end state instead of queueing another fade; platforms
+    // without per-window opacity always show instantly
+    if !animated || interrupted || !opacity::SUPPORTED {
+        window.state::<Fade>().finish(generation);
+        if opacity::SUPPORTED {
+            set_opacity(window, target);
+        }
+        return crate::show_and_focus(window);
+    }
+
+    set_opacity(window, 0.0);
+    crate::show_and_focus(window)?;
+    let window = window.clone();
+    thread::spawn(move || {
+        if ramp(&window, generation, 0.0, target) {
+            window.state::<Fade>().finish(generation);
+        }
+    });
+    Ok(())
+}
+
+// Hide the window and restore focus to the previous app, fading out when animations are enabled
+pub fn hide(window: &Window) -> tauri::Result<()> {
+    let target = resting_opacity(window);
+    let animated = animations_enabled(window);
+    let (generation, interrupted) = window.state::<Fade>().begin(Direction::Out, animated);
+
+    if !animated || interrupted || !opacity::SUPPORTED {
+        window.state::<Fade>().finish(generation);
+        crate::hide_and_restore_focus(window)?;
+        if opacity::SUPPORTED {
+            set_opacity(window, target);
+        }
+        return Ok(());
+    }
+
+    let window = window.clone();
+    thread::spawn(move || {
+        if !ramp(&window, generation, target, 0.0) {
+            return;
+        }
+        if let Err(err) = crate::hide_and_restore_focus(&window) {
+            tracing::error!("Failed to hide window: {}", err);
+        }
+        // Reset while hidden so the next instant show is not invisible
+        set_opacity(&window, target);
+        window.state::<Fade>().finish(generation);
+    });
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+// Bytes read from the start of a file to recognise its format
+const SNIFF_BYTES: u64 = 512;
+
+// Errors returned to the frontend by file_metadata
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum MetadataError {
+    NotFound(String),
+    Unreadable(String),
+}
+
+// What the UI shows about a file before acting on it; timestamps the filesystem does not
+// record are null
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetadata {
+    pub size: u64,
+    pub created: Option<String>,
+    pub modified: Option<String>,
+    pub accessed: Option<String>,
+    pub readonly: bool,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    pub mime: Option<String>,
+}
+
+// UTC timestamp such as 2024-05-01T09:30:00.250Z; days to date after Howard Hinnant's
+// civil_from_days
+pub(crate) fn iso8601(time: SystemTime) -> Option<String> {
+    let elapsed = time.duration_since(UNIX_EPOCH).ok()?;
+    let seconds = elapsed.as_secs();
+    let days = (seconds / 86_400) as i64;
+    let time_of_day = seconds % 86_400;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        time_of_day / 3600,
+        time_of_day / 60 % 60,
+        time_of_day % 60,
+        elapsed.subsec_millis()
+    ))
+}
+
+// Formats recognisable from their first bytes
+fn sniff(header: &[u8]) -> Option<&'static str> {
+    let signatures: [(&[u8], &str); 12] = [
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF8", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+        (b"ID3", "audio/mpeg"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+        (
+            b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1",
+            "application/x-ole-storage",
+        ),
+        (b"MZ", "application/vnd.microsoft.portable-executable"),
+    ];
+    if let Some((_, mime)) = signatures
+        .iter()
+        .find(|(signature, _)| header.starts_with(signature))
+    {
+        return Some(mime);
+    }
+    if header.len() >= 12 && header.starts_with(b"RIFF") {
+        match &header[8..12] {
+            b"WAVE" => return Some("audio/wav"),
+            b"WEBP" => return Some("image/webp"),
+            _ => {}
+        }
+    }
+    if header.len() >= 12 && &header[4..8] == b"ftyp" {
+        return Some("video/mp4");
+    }
+    None
+}
+
+fn mime_from_extension(extension: &str) -> Option<&'static str> {
+    let mime = match extension.to_lowercase().as_str() {
+        "txt" | "log" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "ts" | "tsx" => "text/typescript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "yaml" | "yml" => "application/yaml",
+        "rtf" => "application/rtf",
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "xls" => "application/vnd.ms-excel",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "ods" => "application/vnd.oasis.opendocument.spreadsheet",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "flac" => "audio/flac",
+        "mp4" | "m4v" => "video/mp4",
+        "mov" => "video/quicktime",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "7z" => "application/x-7z-compressed",
+        "exe" | "dll" => "application/vnd.microsoft.portable-executable",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+// UTF-8 without NUL bytes, allowing a character cut off at the end of the sample
+fn looks_like_text(header: &[u8]) -> bool {
+    let utf8 = match std::str::from_utf8(header) {
+        Ok(_) => true,
+        Err(err) => err.error_len().is_none(),
+    };
+    utf8 && !header.is_empty() && !header.contains(&0)
+}
+
+// Trust the content over the extension, except for containers such as zip and the old Office
+// format whose extension names the document inside
+fn infer_mime(path: &Path) -> &'static str {
+    let mut header = Vec::new();
+    if let Ok(file) = File::open(path) {
+        let _ = file.take(SNIFF_BYTES).read_to_end(&mut header);
+    }
+    let by_extension = path
+        .extension()
+        .and_then(|extension| mime_from_extension(&extension.to_string_lossy()));
+    match (sniff(&header), by_extension) {
+        (Some("application/zip" | "application/x-ole-storage"), Some(by_extension)) => by_extension,
+        (Some(sniffed), _) => sniffed,
+        (None, Some(by_extension)) => by_extension,
+        (None, None) if looks_like_text(&header) => "text/plain",
+        (None, None) => "application/octet-stream",
+    }
+}
+
+// Size, timestamps, permissions and type of a file or directory
+#[tauri::command]
+pub async fn file_metadata(path: String) -> Result<FileMetadata, MetadataError> {
+    let path = Path::new(&path);
+    let link = fs::symlink_metadata(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => {
+            MetadataError::NotFound(format!("'{}' does not exist", path.display()))
+        }
+        _ => MetadataError::Unreadable(format!("'{}': {}", path.display(), err)),
+    })?;
+    // Describe what a link points to, or the link itself when it is broken
+    let metadata = fs::metadata(path).unwrap_or_else(|_| link.clone());
+
+    Ok(FileMetadata {
+        size: if metadata.is_dir() { 0 } else { metadata.len() },
+        created: metadata.created().ok().and_then(iso8601),
+        modified: metadata.modified().ok().and_then(iso8601),
+        accessed: metadata.accessed().ok().and_then(iso8601),
+        readonly: metadata.permissions().readonly(),
+        is_dir: metadata.is_dir(),
+        is_symlink: link.file_type().is_symlink(),
+        mime: if metadata.is_file() {
+            Some(infer_mime(path).to_string())
+        } else {
+            None
+        },
+    })
+}
@@ -0,0 +1,427 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::files;
+
+// Copies at least this large report file-op-progress events
+const PROGRESS_MIN_BYTES: u64 = 8 * 1024 * 1024;
+
+// How often a large copy reports its progress
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+const COPY_BUFFER_BYTES: usize = 1024 * 1024;
+
+// rename() error for a destination on another filesystem: EXDEV, or ERROR_NOT_SAME_DEVICE
+#[cfg(target_os = "windows")]
+const CROSS_DEVICE_ERROR: i32 = 17;
+#[cfg(not(target_os = "windows"))]
+const CROSS_DEVICE_ERROR: i32 = 18;
+
+// What to do when the destination already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverwritePolicy {
+    Fail,
+    Overwrite,
+    // Pick a free name such as "report (1).pdf"
+    Rename,
+}
+
+// Errors returned to the frontend by copy_file and move_file
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum FileOpError {
+    NotFound(String),
+    AlreadyExists(String),
+    Denied(String),
+    Failed(String),
+}
+
+// Bytes copied so far by a large copy or cross-filesystem move
+#[derive(Debug, Clone, Serialize)]
+pub struct FileOpProgress {
+    pub source: String,
+    pub destination: String,
+    pub copied: u64,
+    pub total: u64,
+}
+
+// "name (n).ext" for the nth alternative to a taken file name
+fn numbered_name(path: &Path, n: u32) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(extension) => format!("{} ({}).{}", stem, n, extension.to_string_lossy()),
+        None => format!("{} ({})", stem, n),
+    };
+    path.with_file_name(name)
+}
+
+fn free_name(path: &Path) -> Result<PathBuf, FileOpError> {
+    (1..10_000)
+        .map(|n| numbered_name(path, n))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .ok_or_else(|| {
+            FileOpError::AlreadyExists(format!("No free name next to '{}'", path.display()))
+        })
+}
+
+// A regular source file, checked before anything is written
+fn check_source(source: &Path) -> Result<u64, FileOpError> {
+    let metadata = fs::metadata(source).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => {
+            FileOpError::NotFound(format!("'{}' does not exist", source.display()))
+        }
+        _ => FileOpError::Failed(format!("'{}': {}", source.display(), err)),
+    })?;
+    if !metadata.is_file() {
+        return Err(FileOpError::Failed(format!(
+            "'{}' is not a file",
+            source.display()
+        )));
+    }
+    Ok(metadata.len())
+}
+
+// Resolve the destination inside the documents and app data directories, apply the overwrite
+// policy and make sure its directory can be written to
+fn resolve_destination(
+    app: &AppHandle,
+    source: &Path,
+    destination: &Path,
+    policy: OverwritePolicy,
+) -> Result<PathBuf, FileOpError> {
+    let (parent, name) = match (destination.parent(), destination.file_name()) {
+        (Some(parent), Some(name)) => (parent, name),
+        _ => {
+            return Err(FileOpError::Failed(format!(
+                "'{}' is not a file path",
+                destination.display()
+            )))
+        }
+    };
+    let parent = parent
+        .canonicalize()
+        .map_err(|err| FileOpError::NotFound(format!("'{}': {}", parent.display(), err)))?;
+    let metadata = fs::metadata(&parent)
+        .map_err(|err| FileOpError::Failed(format!("'{}': {}", parent.display(), err)))?;
+    if !metadata.is_dir() || metadata.permissions().readonly() {
+        return Err(FileOpError::Denied(format!(
+            "'{}' is not a writable directory",
+            parent.display()
+        )));
+    }
+    if !files::writable_roots(app)
+        .iter()
+        .any(|root| parent.starts_with(root))
+    {
+        return Err(FileOpError::Denied(format!(
+            "'{}' is outside the documents and app data directories",
+            destination.display()
+        )));
+    }
+
+    let target = parent.join(name);
+    if source.canonicalize().ok().as_ref() == Some(&target) {
+        return Err(FileOpError::Failed(format!(
+            "'{}' is the source file",
+            target.display()
+        )));
+    }
+    if fs::symlink_metadata(&target).is_err() {
+        return Ok(target);
+    }
+    match policy {
+        OverwritePolicy::Fail => Err(FileOpError::AlreadyExists(format!(
+            "'{}' already exists",
+            target.display()
+        ))),
+        OverwritePolicy::Overwrite if target.is_dir() => Err(FileOpError::Failed(format!(
+            "'{}' is a directory",
+            target.display()
+        ))),
+        OverwritePolicy::Overwrite => Ok(target),
+        OverwritePolicy::Rename => free_name(&target),
+    }
+}
+
+// Copy through a temporary file next to the target so a failed copy never leaves a partial one,
+// reporting progress for large files
+fn copy_contents(
+    app: &AppHandle,
+    source: &Path,
+    target: &Path,
+    total: u64,
+) -> Result<(), FileOpError> {
+    let file_name = target.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = target.with_file_name(format!(".{}.{}.tmp", file_name, process::id()));
+    let progress = |copied: u64| {
+        if let Some(window) = crate::main_window(app) {
+            let payload = FileOpProgress {
+                source: source.to_string_lossy().to_string(),
+                destination: target.to_string_lossy().to_string(),
+                copied,
+                total,
+            };
+            window
+                .emit("file-op-progress", payload)
+                .unwrap_or_else(|err| tracing::error!("Failed to emit copy progress: {}", err));
+        }
+    };
+
+    let result = (|| -> io::Result<()> {
+        let mut reader = File::open(source)?;
+        let mut writer = File::create(&temp_path)?;
+        let mut buffer = vec![0; COPY_BUFFER_BYTES];
+        let mut copied = 0;
+        let mut last_progress = Instant::now();
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            writer.write_all(&buffer[..read])?;
+            copied += read as u64;
+            if total >= PROGRESS_MIN_BYTES && last_progress.elapsed() >= PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                progress(copied);
+            }
+        }
+        writer.sync_all()?;
+        fs::set_permissions(&temp_path, reader.metadata()?.permissions())?;
+        fs::rename(&temp_path, target)?;
+        if total >= PROGRESS_MIN_BYTES {
+            progress(copied);
+        }
+        Ok(())
+    })();
+    result.map_err(|err| {
+        let _ = fs::remove_file(&temp_path);
+        FileOpError::Failed(format!(
+            "Failed to copy '{}' to '{}': {}",
+            source.display(),
+            target.display(),
+            err
+        ))
+    })
+}
+
+// Rename source to target, falling back to a copy and removing the original when they are on
+// different filesystems
+fn rename_or_copy(
+    source: &Path,
+    target: &Path,
+    rename: impl FnOnce(&Path, &Path) -> io::Result<()>,
+    copy: impl FnOnce() -> Result<(), FileOpError>,
+) -> Result<(), FileOpError> {
+    match rename(source, target) {
+        Ok(()) => Ok(()),
+        Err(err) if err.raw_os_error() == Some(CROSS_DEVICE_ERROR) => {
+            copy()?;
+            fs::remove_file(source).map_err(|err| {
+                FileOpError::Failed(format!(
+                    "Copied to '{}' but could not remove '{}': {}",
+                    target.display(),
+                    source.display(),
+                    err
+                ))
+            })
+        }
+        Err(err) => Err(FileOpError::Failed(format!(
+            "Failed to move '{}' to '{}': {}",
+            source.display(),
+            target.display(),
+            err
+        ))),
+    }
+}
+
+fn copy(
+    app: &AppHandle,
+    source: &Path,
+    destination: &Path,
+    overwrite: OverwritePolicy,
+) -> Result<PathBuf, FileOpError> {
+    let total = check_source(source)?;
+    let target = resolve_destination(app, source, destination, overwrite)?;
+    copy_contents(app, source, &target, total)?;
+    Ok(target)
+}
+
+fn move_to(
+    app: &AppHandle,
+    source: &Path,
+    destination: &Path,
+    overwrite: OverwritePolicy,
+) -> Result<PathBuf, FileOpError> {
+    let total = check_source(source)?;
+    // Files may come from anywhere in the home directory, e.g. Downloads being organized
+    let in_home = source.canonicalize().map_or(false, |source| {
+        files::home_roots(app)
+            .iter()
+            .any(|root| source.starts_with(root))
+    });
+    if !in_home {
+        return Err(FileOpError::Denied(format!(
+            "'{}' is outside the home directory",
+            source.display()
+        )));
+    }
+    let target = resolve_destination(app, source, destination, overwrite)?;
+    rename_or_copy(
+        source,
+        &target,
+        |from, to| fs::rename(from, to),
+        || copy_contents(app, source, &target, total),
+    )?;
+    Ok(target)
+}
+
+// Run a file operation off the async runtime's threads, since a large copy blocks for a while
+async fn run_blocking(
+    operation: impl FnOnce() -> Result<PathBuf, FileOpError> + Send + 'static,
+) -> Result<String, FileOpError> {
+    let target = tauri::async_runtime::spawn_blocking(operation)
+        .await
+        .map_err(|err| FileOpError::Failed(err.to_string()))??;
+    Ok(target.to_string_lossy().to_string())
+}
+
+// Copy a file into the documents or app data directories and return where it ended up
+#[tauri::command]
+pub async fn copy_file(
+    app: AppHandle,
+    src: String,
+    dst: String,
+    overwrite: OverwritePolicy,
+) -> Result<String, FileOpError> {
+    run_blocking(move || copy(&app, Path::new(&src), Path::new(&dst), overwrite)).await
+}
+
+// Move a file from the home directory into the documents or app data directories and return
+// where it ended up; a move to another filesystem copies the file and then removes the original
+#[tauri::command]
+pub async fn move_file(
+    app: AppHandle,
+    src: String,
+    dst: String,
+    overwrite: OverwritePolicy,
+) -> Result<String, FileOpError> {
+    run_blocking(move || move_to(&app, Path::new(&src), Path::new(&dst), overwrite)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A scratch directory under the temp dir, removed when dropped
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Scratch {
+            let dir = std::env::temp_dir().join(format!("aura-{}-{}", name, process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Scratch(dir)
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn numbers_names_before_the_extension() {
+        let dir = Path::new("docs");
+        assert_eq!(
+            numbered_name(&dir.join("report.pdf"), 1),
+            dir.join("report (1).pdf")
+        );
+        assert_eq!(
+            numbered_name(&dir.join("archive.tar.gz"), 2),
+            dir.join("archive.tar (2).gz")
+        );
+        assert_eq!(numbered_name(&dir.join("notes"), 3), dir.join("notes (3)"));
+        assert_eq!(
+            numbered_name(&dir.join(".profile"), 1),
+            dir.join(".profile (1)")
+        );
+    }
+
+    #[test]
+    fn free_name_skips_taken_numbers() {
+        let scratch = Scratch::new("free-name");
+        let target = scratch.0.join("report.pdf");
+        for name in ["report.pdf", "report (1).pdf", "report (2).pdf"] {
+            fs::write(scratch.0.join(name), "").unwrap();
+        }
+        assert_eq!(
+            free_name(&target).unwrap(),
+            scratch.0.join("report (3).pdf")
+        );
+    }
+
+    #[test]
+    fn cross_device_rename_copies_and_removes_the_source() {
+        let scratch = Scratch::new("cross-device");
+        let source = scratch.0.join("source.txt");
+        let target = scratch.0.join("target.txt");
+        fs::write(&source, "contents").unwrap();
+
+        let result = rename_or_copy(
+            &source,
+            &target,
+            |_, _| Err(io::Error::from_raw_os_error(CROSS_DEVICE_ERROR)),
+            || {
+                fs::copy(&source, &target)
+                    .map(|_| ())
+                    .map_err(|err| FileOpError::Failed(err.to_string()))
+            },
+        );
+        assert!(result.is_ok());
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "contents");
+    }
+
+    #[test]
+    fn failed_cross_device_copy_keeps_the_source() {
+        let scratch = Scratch::new("cross-device-failed");
+        let source = scratch.0.join("source.txt");
+        fs::write(&source, "contents").unwrap();
+
+        let result = rename_or_copy(
+            &source,
+            &scratch.0.join("target.txt"),
+            |_, _| Err(io::Error::from_raw_os_error(CROSS_DEVICE_ERROR)),
+            || Err(FileOpError::Failed("disk full".to_string())),
+        );
+        assert!(matches!(result, Err(FileOpError::Failed(message)) if message == "disk full"));
+        assert!(source.exists());
+    }
+
+    #[test]
+    fn other_rename_errors_do_not_copy() {
+        let mut copied = false;
+        let result = rename_or_copy(
+            Path::new("source.txt"),
+            Path::new("target.txt"),
+            |_, _| Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied")),
+            || {
+                copied = true;
+                Ok(())
+            },
+        );
+        assert!(matches!(result, Err(FileOpError::Failed(_))));
+        assert!(!copied);
+    }
+}
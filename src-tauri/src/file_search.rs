@@ -0,0 +1,244 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use globset::{GlobBuilder, GlobMatcher};
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+use walkdir::{DirEntry, WalkDir};
+
+use crate::files;
+
+// How deep below the root a search descends
+const MAX_DEPTH: usize = 12;
+
+// Folders that hold app or system data rather than the user's documents
+const SKIPPED_DIRS: [&str; 6] = [
+    "node_modules",
+    "AppData",
+    "Library",
+    "$Recycle.Bin",
+    "System Volume Information",
+    "__pycache__",
+];
+
+// How often found files are sent to the frontend while a search runs
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+// A file matching the pattern; modified is in epoch milliseconds
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub path: String,
+    pub size: u64,
+    pub modified: Option<u64>,
+}
+
+// Files found since the last search-progress event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchProgress {
+    pub task_id: u64,
+    pub hits: Vec<SearchHit>,
+    pub scanned: u64,
+}
+
+// Every hit of a finished search, most recently modified first
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFinished {
+    pub task_id: u64,
+    pub results: Vec<SearchHit>,
+    pub cancelled: bool,
+    pub truncated: bool,
+}
+
+// Cancellation flags of the searches in progress, by task id
+#[derive(Default)]
+pub struct Searches {
+    running: Mutex<HashMap<u64, Arc<AtomicBool>>>,
+    next_id: AtomicU64,
+}
+
+fn is_skipped(entry: &DirEntry) -> bool {
+    if entry.depth() == 0 || !entry.file_type().is_dir() {
+        return false;
+    }
+    let name = entry.file_name().to_string_lossy();
+    if SKIPPED_DIRS
+        .iter()
+        .any(|skipped| name.eq_ignore_ascii_case(skipped))
+    {
+        return true;
+    }
+    entry
+        .metadata()
+        .map_or(false, |metadata| files::is_hidden(&name, &metadata))
+}
+
+fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    if let Some(window) = crate::main_window(app) {
+        window
+            .emit(event, payload)
+            .unwrap_or_else(|err| tracing::error!("Failed to emit {}: {}", event, err));
+    }
+}
+
+// Patterns with a slash match the path below the root, others only the file name
+fn matches(matcher: &GlobMatcher, by_path: bool, root: &Path, entry: &DirEntry) -> bool {
+    if by_path {
+        entry
+            .path()
+            .strip_prefix(root)
+            .map_or(false, |relative| matcher.is_match(relative))
+    } else {
+        matcher.is_match(entry.file_name())
+    }
+}
+
+fn search(
+    app: &AppHandle,
+    task_id: u64,
+    root: &Path,
+    matcher: &GlobMatcher,
+    by_path: bool,
+    max_results: usize,
+    cancelled: &AtomicBool,
+) -> SearchFinished {
+    let mut results = Vec::new();
+    let mut pending = Vec::new();
+    let mut scanned = 0;
+    let mut last_progress = Instant::now();
+    let mut truncated = false;
+
+    let walker = WalkDir::new(root)
+        .max_depth(MAX_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| !is_skipped(entry));
+    for entry in walker {
+        if cancelled.load(Ordering::SeqCst) {
+            break;
+        }
+        // Folders that cannot be read are left out rather than failing the search
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        scanned += 1;
+        if !entry.file_type().is_file() || !matches(matcher, by_path, root, &entry) {
+            continue;
+        }
+        if results.len() == max_results {
+            truncated = true;
+            break;
+        }
+
+        let metadata = entry.metadata().ok();
+        let hit = SearchHit {
+            path: entry.path().to_string_lossy().to_string(),
+            size: metadata.as_ref().map_or(0, |metadata| metadata.len()),
+            modified: metadata.as_ref().and_then(files::modified_millis),
+        };
+        pending.push(hit.clone());
+        results.push(hit);
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            let hits = std::mem::take(&mut pending);
+            emit(
+                app,
+                "search-progress",
+                SearchProgress {
+                    task_id,
+                    hits,
+                    scanned,
+                },
+            );
+        }
+    }
+    if !pending.is_empty() {
+        emit(
+            app,
+            "search-progress",
+            SearchProgress {
+                task_id,
+                hits: pending,
+                scanned,
+            },
+        );
+    }
+
+    results.sort_by_key(|hit| Reverse(hit.modified));
+    SearchFinished {
+        task_id,
+        results,
+        cancelled: cancelled.load(Ordering::SeqCst),
+        truncated,
+    }
+}
+
+// Search a folder tree for files matching a glob such as "*budget*.xlsx", case-insensitively.
+// Returns a task id at once; hits follow as search-progress events and the sorted results as
+// search-finished
+#[tauri::command]
+pub fn glob_search(
+    app: AppHandle,
+    searches: State<'_, Searches>,
+    root: String,
+    pattern: String,
+    max_results: usize,
+) -> Result<u64, String> {
+    let root = Path::new(&root)
+        .canonicalize()
+        .map_err(|err| format!("'{}': {}", root, err))?;
+    if !root.is_dir() {
+        return Err(format!("'{}' is not a directory", root.display()));
+    }
+    let matcher = GlobBuilder::new(&pattern)
+        .case_insensitive(true)
+        .literal_separator(true)
+        .build()
+        .map_err(|err| format!("Invalid pattern '{}': {}", pattern, err))?
+        .compile_matcher();
+    let by_path = pattern.contains('/');
+
+    let task_id = searches.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+    let cancelled = Arc::new(AtomicBool::new(false));
+    searches
+        .running
+        .lock()
+        .unwrap()
+        .insert(task_id, cancelled.clone());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let finished = search(
+            &app,
+            task_id,
+            &root,
+            &matcher,
+            by_path,
+            max_results,
+            &cancelled,
+        );
+        app.state::<Searches>()
+            .running
+            .lock()
+            .unwrap()
+            .remove(&task_id);
+        emit(&app, "search-finished", finished);
+    });
+    Ok(task_id)
+}
+
+// Stop a search started by glob_search; it still sends search-finished with what it found
+#[tauri::command]
+pub fn cancel_search(searches: State<'_, Searches>, task_id: u64) -> Result<(), String> {
+    let running = searches.running.lock().unwrap();
+    let cancelled = running
+        .get(&task_id)
+        .ok_or_else(|| format!("No search with id {}", task_id))?;
+    cancelled.store(true, Ordering::SeqCst);
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+// Quiet period after the last change before file-changed is sent; editors save in bursts
+const FILE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+// Quiet period before fs-change is sent; downloads and copies arrive as many events
+const PATH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+// A watched path changed, with the path as the frontend passed it to watch_file
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChanged {
+    pub path: String,
+}
+
+// What happened to the paths of an fs-change event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed,
+}
+
+// Changes under a watch_path watch, one event per kind for each burst
+#[derive(Debug, Clone, Serialize)]
+pub struct FsChange {
+    pub id: String,
+    pub kind: ChangeKind,
+    pub paths: Vec<String>,
+}
+
+// Watchers keyed by the path passed to watch_file; dropping one releases its OS handle
+#[derive(Default)]
+pub struct FileWatches(Mutex<HashMap<String, RecommendedWatcher>>);
+
+// Watchers keyed by the id passed to watch_path
+#[derive(Default)]
+pub struct PathWatches(Mutex<HashMap<String, RecommendedWatcher>>);
+
+// True for a change to the watched entry; name is set when a file is watched via its directory
+fn is_change(event: &Event, name: Option<&OsString>) -> bool {
+    if event.kind.is_access() {
+        return false;
+    }
+    match name {
+        Some(name) => event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == Some(name.as_os_str())),
+        None => true,
+    }
+}
+
+fn change_kind(kind: &EventKind) -> Option<ChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(ChangeKind::Created),
+        EventKind::Modify(ModifyKind::Name(_)) => Some(ChangeKind::Renamed),
+        EventKind::Modify(_) | EventKind::Any => Some(ChangeKind::Modified),
+        EventKind::Remove(_) => Some(ChangeKind::Removed),
+        EventKind::Access(_) | EventKind::Other => None,
+    }
+}
+
+// Collect changes until a quiet period passes, then hand over the burst; ends once the watcher
+// and its sender are dropped, discarding a burst still in progress
+fn debounce<T>(changes: Receiver<T>, quiet: Duration, mut flush: impl FnMut(Vec<T>)) {
+    while let Ok(first) = changes.recv() {
+        let mut burst = vec![first];
+        loop {
+            match changes.recv_timeout(quiet) {
+                Ok(change) => burst.push(change),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        flush(burst);
+    }
+}
+
+fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    if let Some(window) = crate::main_window(app) {
+        window
+            .emit(event, payload)
+            .unwrap_or_else(|err| tracing::error!("Failed to emit {}: {}", event, err));
+    }
+}
+
+fn canonical(path: &str) -> Result<PathBuf, String> {
+    fs::canonicalize(path).map_err(|err| format!("Cannot watch '{}': {}", path, err))
+}
+
+fn start(app: AppHandle, path: String) -> Result<RecommendedWatcher, String> {
+    let target = canonical(&path)?;
+
+    // Editors often save by replacing the file, which would leave a watch on the old one
+    // behind, so files are watched through their directory
+    let (watched, name) = if target.is_dir() {
+        (target, None)
+    } else {
+        let dir = target
+            .parent()
+            .ok_or_else(|| format!("Cannot watch '{}'", path))?
+            .to_path_buf();
+        (dir, target.file_name().map(OsString::from))
+    };
+
+    let (sender, changes) = mpsc::channel();
+    let mut watcher =
+        notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+            Ok(event) => {
+                if is_change(&event, name.as_ref()) {
+                    let _ = sender.send(());
+                }
+            }
+            Err(err) => tracing::warn!("File watcher error: {}", err),
+        })
+        .map_err(|err| format!("Failed to watch '{}': {}", path, err))?;
+    watcher
+        .watch(&watched, RecursiveMode::NonRecursive)
+        .map_err(|err| format!("Failed to watch '{}': {}", path, err))?;
+
+    thread::spawn(move || {
+        debounce(changes, FILE_DEBOUNCE, |_| {
+            emit(&app, "file-changed", FileChanged { path: path.clone() })
+        })
+    });
+    Ok(watcher)
+}
+
+fn start_path(
+    app: AppHandle,
+    id: String,
+    path: &str,
+    recursive: bool,
+) -> Result<RecommendedWatcher, String> {
+    let target = canonical(path)?;
+    let (sender, changes) = mpsc::channel();
+    let mut watcher =
+        notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+            Ok(event) => {
+                if let Some(kind) = change_kind(&event.kind) {
+                    let _ = sender.send((kind, event.paths));
+                }
+            }
+            Err(err) => tracing::warn!("File watcher error: {}", err),
+        })
+        .map_err(|err| format!("Failed to watch '{}': {}", path, err))?;
+    let mode = if recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher
+        .watch(&target, mode)
+        .map_err(|err| format!("Failed to watch '{}': {}", path, err))?;
+
+    thread::spawn(move || {
+        debounce(changes, PATH_DEBOUNCE, |burst| {
+            let mut by_kind: BTreeMap<ChangeKind, BTreeSet<PathBuf>> = BTreeMap::new();
+            for (kind, paths) in burst {
+                by_kind.entry(kind).or_default().extend(paths);
+            }
+            for (kind, paths) in by_kind {
+                let paths = paths
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect();
+                let id = id.clone();
+                emit(&app, "fs-change", FsChange { id, kind, paths });
+            }
+        })
+    });
+    Ok(watcher)
+}
+
+// Drop every watcher on the way out so their OS handles and threads go with them
+pub fn stop_all(app: &AppHandle) {
+    app.state::<FileWatches>().0.lock().unwrap().clear();
+    app.state::<PathWatches>().0.lock().unwrap().clear();
+}
+
+// Emit file-changed whenever the file or directory at path changes
+#[tauri::command]
+pub fn watch_file(
+    app: AppHandle,
+    watches: State<'_, FileWatches>,
+    path: String,
+) -> Result<(), String> {
+    let mut watches = watches.0.lock().unwrap();
+    if watches.contains_key(&path) {
+        return Ok(());
+    }
+    let watcher = start(app, path.clone())?;
+    watches.insert(path, watcher);
+    Ok(())
+}
+
+// Stop watching a path passed to watch_file
+#[tauri::command]
+pub fn unwatch_file(watches: State<'_, FileWatches>, path: String) -> Result<(), String> {
+    let watcher = watches.0.lock().unwrap().remove(&path);
+    watcher
+        .map(drop)
+        .ok_or_else(|| format!("'{}' is not being watched", path))
+}
+
+// Emit fs-change events for a file or directory, under an id of the caller's choosing; an id
+// that is already watching is moved to the new path
+#[tauri::command]
+pub fn watch_path(
+    app: AppHandle,
+    watches: State<'_, PathWatches>,
+    id: String,
+    path: String,
+    recursive: bool,
+) -> Result<(), String> {
+    let watcher = start_path(app, id.clone(), &path, recursive)?;
+    watches.0.lock().unwrap().insert(id, watcher);
+    Ok(())
+}
+
+// Stop a watch_path watch
+#[tauri::command]
+pub fn unwatch_path(watches: State<'_, PathWatches>, id: String) -> Result<(), String> {
+    let watcher = watches.0.lock().unwrap().remove(&id);
+    watcher
+        .map(drop)
+        .ok_or_else(|| format!("No watch with id '{}'", id))
+}
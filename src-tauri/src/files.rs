@@ -0,0 +1,622 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process;
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::audit;
+
+// Most entries list_directory returns from a large folder, e.g. node_modules
+const MAX_LISTED_ENTRIES: usize = 5000;
+
+// Bytes read_text_file returns unless the caller asks for a different limit
+const DEFAULT_MAX_READ_BYTES: u64 = 5 * 1024 * 1024;
+
+// Errors returned to the frontend by read_text_file
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum ReadError {
+    Denied(String),
+    Unreadable(String),
+    NotText(String),
+}
+
+// Encoding a text file was decoded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TextEncoding {
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "utf-16le")]
+    Utf16Le,
+    #[serde(rename = "utf-16be")]
+    Utf16Be,
+    #[serde(rename = "latin-1")]
+    Latin1,
+}
+
+// Decoded text file; size is the full size on disk even when only the start was read
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextFile {
+    pub content: String,
+    pub truncated: bool,
+    pub encoding: TextEncoding,
+    pub size: u64,
+}
+
+// Resolve a path with every link along it followed and require the result to lie inside the
+// user's home directory, so neither the file nor a linked folder above it can lead out
+fn resolve_in_home(path: &Path) -> Result<PathBuf, ReadError> {
+    let resolved = path
+        .canonicalize()
+        .map_err(|err| ReadError::Unreadable(format!("'{}': {}", path.display(), err)))?;
+    let home = tauri::api::path::home_dir()
+        .ok_or_else(|| ReadError::Denied("Home directory is unavailable".to_string()))?;
+    let home = home.canonicalize().unwrap_or(home);
+    if !resolved.starts_with(&home) {
+        return Err(ReadError::Denied(format!(
+            "'{}' is outside the home directory",
+            path.display()
+        )));
+    }
+    Ok(resolved)
+}
+
+fn decode_utf16(bytes: &[u8], encoding: TextEncoding, truncated: bool) -> Option<String> {
+    let mut units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| match encoding {
+            TextEncoding::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+            _ => u16::from_le_bytes([pair[0], pair[1]]),
+        })
+        .collect();
+    // The cut may fall between the two halves of a surrogate pair
+    if truncated && matches!(units.last(), Some(0xD800..=0xDBFF)) {
+        units.pop();
+    }
+    String::from_utf16(&units).ok()
+}
+
+// Decode by byte order mark, then as UTF-8, then as Latin-1; None for binary content
+fn decode(bytes: &[u8], truncated: bool) -> Option<(String, TextEncoding)> {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return decode_utf8(rest, truncated).map(|text| (text, TextEncoding::Utf8));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let encoding = TextEncoding::Utf16Le;
+        return decode_utf16(rest, encoding, truncated).map(|text| (text, encoding));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let encoding = TextEncoding::Utf16Be;
+        return decode_utf16(rest, encoding, truncated).map(|text| (text, encoding));
+    }
+
+    // Without a BOM, NUL bytes and other control characters mean a binary file
+    let is_binary = |byte: &u8| byte.is_ascii_control() && !b"\t\n\r\x0c\x1b".contains(byte);
+    if bytes.iter().any(is_binary) {
+        return None;
+    }
+    if let Some(text) = decode_utf8(bytes, truncated) {
+        return Some((text, TextEncoding::Utf8));
+    }
+    let text = bytes.iter().map(|&byte| byte as char).collect();
+    Some((text, TextEncoding::Latin1))
+}
+
+fn decode_utf8(bytes: &[u8], truncated: bool) -> Option<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text.to_string()),
+        // The cut may fall inside a multi-byte character
+        Err(err) if truncated && err.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&bytes[..err.valid_up_to()]).into_owned())
+        }
+        Err(_) => None,
+    }
+}
+
+// Read a text file with normalized line endings, up to max_bytes of it
+#[tauri::command]
+pub async fn read_text_file(path: String, max_bytes: Option<u64>) -> Result<TextFile, ReadError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = resolve_in_home(Path::new(&path))?;
+        read_text(&path, max_bytes.unwrap_or(DEFAULT_MAX_READ_BYTES))
+    })
+    .await
+    .map_err(|err| ReadError::Unreadable(err.to_string()))?
+}
+
+fn read_text(path: &Path, limit: u64) -> Result<TextFile, ReadError> {
+    let unreadable =
+        |err: io::Error| ReadError::Unreadable(format!("'{}': {}", path.display(), err));
+    let metadata = fs::metadata(path).map_err(unreadable)?;
+    if metadata.is_dir() {
+        return Err(ReadError::Unreadable(format!(
+            "'{}' is a directory",
+            path.display()
+        )));
+    }
+
+    let size = metadata.len();
+    let mut bytes = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(limit).read_to_end(&mut bytes))
+        .map_err(unreadable)?;
+    let truncated = size > bytes.len() as u64;
+
+    let (content, encoding) = decode(&bytes, truncated)
+        .ok_or_else(|| ReadError::NotText(format!("'{}' is not a text file", path.display())))?;
+    Ok(TextFile {
+        content: content.replace("\r\n", "\n").replace('\r', "\n"),
+        truncated,
+        encoding,
+        size,
+    })
+}
+
+// One entry of a directory listing for the file browser
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirEntry {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: Option<u64>,
+    pub extension: Option<String>,
+    pub is_hidden: bool,
+    pub is_symlink: bool,
+}
+
+// Entry list_directory could not read, with the reason
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedEntry {
+    pub path: String,
+    pub error: String,
+}
+
+// Directory listing; truncated is set when the folder holds more than MAX_LISTED_ENTRIES, of
+// which the first ones in display order are returned
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryListing {
+    pub entries: Vec<DirEntry>,
+    pub skipped: Vec<SkippedEntry>,
+    pub truncated: bool,
+}
+
+// Modification time in epoch milliseconds, where the filesystem records one
+pub(crate) fn modified_millis(metadata: &fs::Metadata) -> Option<u64> {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis() as u64)
+}
+
+// Dotfiles everywhere, plus entries carrying the hidden attribute on Windows
+pub(crate) fn is_hidden(name: &str, metadata: &fs::Metadata) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+        use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_HIDDEN;
+        if metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0 {
+            return true;
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = metadata;
+    name.starts_with('.')
+}
+
+// List a directory with directories first, each group sorted by name. Symlinks are described
+// by the link itself rather than its target
+#[tauri::command]
+pub async fn list_directory(
+    path: String,
+    include_hidden: bool,
+) -> Result<DirectoryListing, String> {
+    tauri::async_runtime::spawn_blocking(move || list(&path, include_hidden))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+fn list(path: &str, include_hidden: bool) -> Result<DirectoryListing, String> {
+    let path = Path::new(path)
+        .canonicalize()
+        .map_err(|err| format!("'{}': {}", path, err))?;
+    if !path.is_dir() {
+        return Err(format!("'{}' is not a directory", path.display()));
+    }
+
+    let entries =
+        fs::read_dir(&path).map_err(|err| format!("Cannot read '{}': {}", path.display(), err))?;
+    let mut listing = DirectoryListing {
+        entries: Vec::new(),
+        skipped: Vec::new(),
+        truncated: false,
+    };
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                listing.skipped.push(SkippedEntry {
+                    path: path.to_string_lossy().to_string(),
+                    error: err.to_string(),
+                });
+                continue;
+            }
+        };
+        let entry_path = entry.path();
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                listing.skipped.push(SkippedEntry {
+                    path: entry_path.to_string_lossy().to_string(),
+                    error: err.to_string(),
+                });
+                continue;
+            }
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_hidden = is_hidden(&name, &metadata);
+        if is_hidden && !include_hidden {
+            continue;
+        }
+        let is_dir = metadata.is_dir();
+        listing.entries.push(DirEntry {
+            path: entry_path.to_string_lossy().to_string(),
+            is_dir,
+            size: if is_dir { 0 } else { metadata.len() },
+            modified: modified_millis(&metadata),
+            extension: if is_dir {
+                None
+            } else {
+                entry_path
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().to_string())
+            },
+            is_hidden,
+            is_symlink: metadata.file_type().is_symlink(),
+            name,
+        });
+    }
+
+    listing.entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    // Cut after sorting, so a long listing keeps the first entries in display order
+    listing.truncated = listing.entries.len() > MAX_LISTED_ENTRIES;
+    listing.entries.truncate(MAX_LISTED_ENTRIES);
+    Ok(listing)
+}
+
+// Errors returned to the frontend by write_text_file
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum WriteError {
+    Denied {
+        message: String,
+    },
+    // modified is the existing file's modification time in epoch milliseconds
+    AlreadyExists {
+        message: String,
+        modified: Option<u64>,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+fn denied(message: String) -> WriteError {
+    WriteError::Denied { message }
+}
+
+fn failed(message: String) -> WriteError {
+    WriteError::Failed { message }
+}
+
+// Directories the file commands may write into, move within and trash from
+pub(crate) fn writable_roots(app: &AppHandle) -> Vec<PathBuf> {
+    [
+        tauri::api::path::document_dir(),
+        app.path_resolver().app_data_dir(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|dir| dir.canonicalize().ok())
+    .collect()
+}
+
+// The home directory and the writable roots, with links resolved; files may be read, revealed
+// or moved out of anywhere in here
+pub(crate) fn home_roots(app: &AppHandle) -> Vec<PathBuf> {
+    let mut roots = writable_roots(app);
+    roots.extend(tauri::api::path::home_dir().and_then(|home| home.canonicalize().ok()));
+    roots
+}
+
+// Create the missing directories above a file when the part that already exists is allowed
+fn create_parents(roots: &[PathBuf], dir: &Path) -> Result<(), WriteError> {
+    // '..' below a missing directory cannot be resolved before it is created
+    if dir
+        .components()
+        .any(|component| component == Component::ParentDir)
+    {
+        return Err(denied(format!(
+            "'{}' must not contain '..' to create directories",
+            dir.display()
+        )));
+    }
+    let existing = dir
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .and_then(|ancestor| ancestor.canonicalize().ok());
+    match existing {
+        Some(existing) if roots.iter().any(|root| existing.starts_with(root)) => {}
+        _ => {
+            return Err(denied(format!(
+                "'{}' is outside the documents and app data directories",
+                dir.display()
+            )))
+        }
+    }
+    fs::create_dir_all(dir)
+        .map_err(|err| failed(format!("Failed to create '{}': {}", dir.display(), err)))
+}
+
+// Resolve the target through symlinks and make sure it lands in an allowed directory
+fn resolve_writable(
+    app: &AppHandle,
+    path: &Path,
+    create_missing: bool,
+) -> Result<PathBuf, WriteError> {
+    if !path.is_absolute() {
+        return Err(denied(format!(
+            "'{}' is not an absolute path",
+            path.display()
+        )));
+    }
+    let (parent, name) = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => (parent, name),
+        _ => return Err(denied(format!("'{}' is not a file path", path.display()))),
+    };
+    let roots = writable_roots(app);
+    if create_missing && !parent.exists() {
+        create_parents(&roots, parent)?;
+    }
+    let parent = parent
+        .canonicalize()
+        .map_err(|err| failed(format!("'{}': {}", parent.display(), err)))?;
+
+    // An existing target may itself be a link to somewhere else
+    let target = parent.join(name);
+    let target = target.canonicalize().unwrap_or(target);
+
+    if !roots.iter().any(|root| target.starts_with(root)) {
+        return Err(denied(format!(
+            "'{}' is outside the documents and app data directories",
+            path.display()
+        )));
+    }
+    Ok(target)
+}
+
+fn write_text(
+    app: &AppHandle,
+    path: &Path,
+    content: &str,
+    overwrite: bool,
+    create_parents: bool,
+) -> Result<(), WriteError> {
+    let target = resolve_writable(app, path, create_parents)?;
+    if target.is_dir() {
+        return Err(failed(format!("'{}' is a directory", target.display())));
+    }
+    if !overwrite {
+        if let Ok(metadata) = fs::metadata(&target) {
+            return Err(WriteError::AlreadyExists {
+                message: format!("'{}' already exists", target.display()),
+                modified: modified_millis(&metadata),
+            });
+        }
+    }
+
+    // Same directory as the target so the rename cannot cross filesystems
+    let file_name = target.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = target.with_file_name(format!(".{}.{}.tmp", file_name, process::id()));
+
+    let result = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, &target));
+    if let Err(err) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(failed(format!(
+            "Failed to write '{}': {}",
+            target.display(),
+            err
+        )));
+    }
+
+    // The rename itself is only durable once the directory entry is flushed
+    #[cfg(unix)]
+    if let Some(dir) = target.parent() {
+        if let Err(err) = File::open(dir).and_then(|dir| dir.sync_all()) {
+            tracing::warn!("Failed to sync '{}': {}", dir.display(), err);
+        }
+    }
+    Ok(())
+}
+
+// Write a text file through a temporary file so a crash or failed write never leaves the target
+// half written
+#[tauri::command]
+pub async fn write_text_file(
+    app: AppHandle,
+    path: String,
+    content: String,
+    overwrite: bool,
+    create_parents: Option<bool>,
+) -> Result<(), WriteError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        write_text(
+            &app,
+            Path::new(&path),
+            &content,
+            overwrite,
+            create_parents.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|err| failed(err.to_string()))?
+}
+
+// Resolve an existing file or directory strictly inside the documents or app data directories;
+// a link is resolved to where it sits rather than where it points, so it is acted on itself
+pub(crate) fn sandboxed_entry(app: &AppHandle, path: &Path) -> Result<PathBuf, String> {
+    if fs::symlink_metadata(path).is_err() {
+        return Err(format!("'{}' does not exist", path.display()));
+    }
+    let (parent, name) = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => (parent, name),
+        _ => return Err(format!("'{}' cannot be removed", path.display())),
+    };
+    let target = parent
+        .canonicalize()
+        .map_err(|err| format!("'{}': {}", parent.display(), err))?
+        .join(name);
+
+    if !writable_roots(app)
+        .iter()
+        .any(|root| target.starts_with(root) && &target != root)
+    {
+        return Err(format!(
+            "'{}' is outside the documents and app data directories",
+            path.display()
+        ));
+    }
+    Ok(target)
+}
+
+// Move a file or directory to the recycle bin or trash so the user can restore it
+#[tauri::command]
+pub async fn trash_file(app: AppHandle, path: String) -> Result<(), String> {
+    let target = sandboxed_entry(&app, Path::new(&path))?;
+    // Trashing a large folder takes a while, so it runs off the async runtime's threads
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = trash::delete(&target).map_err(|err| {
+            format!(
+                "Failed to move '{}' to the trash: {}",
+                target.display(),
+                err
+            )
+        });
+        audit::record(&app, "trash", &target, &result);
+        result
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str, big_endian: bool) -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|unit| match big_endian {
+                true => unit.to_be_bytes(),
+                false => unit.to_le_bytes(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decodes_utf8_with_and_without_bom() {
+        let text = "naïve café ✓";
+        let plain = text.as_bytes().to_vec();
+        let mut with_bom = vec![0xEF, 0xBB, 0xBF];
+        with_bom.extend_from_slice(&plain);
+
+        assert_eq!(
+            decode(&plain, false),
+            Some((text.to_string(), TextEncoding::Utf8))
+        );
+        assert_eq!(
+            decode(&with_bom, false),
+            Some((text.to_string(), TextEncoding::Utf8))
+        );
+    }
+
+    #[test]
+    fn decodes_utf16_by_bom() {
+        let text = "Grüße 🎤";
+        let mut le = vec![0xFF, 0xFE];
+        le.extend(utf16(text, false));
+        let mut be = vec![0xFE, 0xFF];
+        be.extend(utf16(text, true));
+
+        assert_eq!(
+            decode(&le, false),
+            Some((text.to_string(), TextEncoding::Utf16Le))
+        );
+        assert_eq!(
+            decode(&be, false),
+            Some((text.to_string(), TextEncoding::Utf16Be))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_latin1() {
+        // "café" in Latin-1 is not valid UTF-8
+        let bytes = [b'c', b'a', b'f', 0xE9];
+        assert_eq!(
+            decode(&bytes, false),
+            Some(("café".to_string(), TextEncoding::Latin1))
+        );
+    }
+
+    #[test]
+    fn rejects_binary_content() {
+        assert_eq!(decode(&[b'a', 0, b'b'], false), None);
+    }
+
+    #[test]
+    fn drops_a_character_cut_by_truncation() {
+        // "✓" is three bytes; the cut leaves the first two
+        let bytes = &"ok ✓".as_bytes()[..5];
+        assert_eq!(decode_utf8(bytes, true), Some("ok ".to_string()));
+        assert_eq!(decode_utf8(bytes, false), None);
+
+        // "🎤" is a surrogate pair; the cut leaves the high half
+        let mut units = utf16("ok 🎤", false);
+        units.truncate(units.len() - 2);
+        assert_eq!(
+            decode_utf16(&units, TextEncoding::Utf16Le, true),
+            Some("ok ".to_string())
+        );
+        assert_eq!(decode_utf16(&units, TextEncoding::Utf16Le, false), None);
+    }
+
+    #[test]
+    fn reads_a_truncated_file_without_error() {
+        let path = std::env::temp_dir().join(format!("aura-read-text-{}.txt", process::id()));
+        fs::write(&path, "ok ✓ done").unwrap();
+
+        let result = read_text(&path, 5);
+        fs::remove_file(&path).unwrap();
+
+        let file = result.unwrap();
+        assert_eq!(file.content, "ok ");
+        assert!(file.truncated);
+        assert_eq!(file.encoding, TextEncoding::Utf8);
+        assert_eq!(file.size, 11);
+    }
+}
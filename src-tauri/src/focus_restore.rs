@@ -0,0 +1,114 @@
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+// Application that was in front before Aura took focus
+#[cfg(target_os = "windows")]
+type PreviousApp = isize; // HWND of the foreground window
+#[cfg(target_os = "macos")]
+type PreviousApp = i32; // pid of the frontmost application
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+type PreviousApp = u32; // never captured
+
+// Where focus goes back to when the main window hides
+#[derive(Default)]
+pub struct PreviousFocus(Mutex<Option<PreviousApp>>);
+
+#[cfg(target_os = "windows")]
+fn foreground_app() -> Option<PreviousApp> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd == 0 {
+        return None;
+    }
+    let mut pid = 0;
+    unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+    if pid == std::process::id() {
+        return None;
+    }
+    Some(hwnd)
+}
+
+#[cfg(target_os = "windows")]
+fn activate(hwnd: PreviousApp) {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{IsWindow, SetForegroundWindow};
+
+    // The window may have closed while Aura was open
+    unsafe {
+        if IsWindow(hwnd) != 0 {
+            SetForegroundWindow(hwnd);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn foreground_app() -> Option<PreviousApp> {
+    use cocoa::base::{id, nil};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let frontmost: id = msg_send![workspace, frontmostApplication];
+        if frontmost == nil {
+            return None;
+        }
+        let pid: i32 = msg_send![frontmost, processIdentifier];
+        if pid as u32 == std::process::id() {
+            return None;
+        }
+        Some(pid)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn activate(pid: PreviousApp) {
+    use cocoa::appkit::NSApplicationActivationOptions::NSApplicationActivateIgnoringOtherApps;
+    use cocoa::base::{id, nil, BOOL};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        // nil when the application has quit since
+        let app: id = msg_send![
+            class!(NSRunningApplication),
+            runningApplicationWithProcessIdentifier: pid
+        ];
+        if app != nil {
+            let _: BOOL = msg_send![
+                app,
+                activateWithOptions: NSApplicationActivateIgnoringOtherApps as u64
+            ];
+        }
+    }
+}
+
+// The window manager picks the next window on other platforms
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn foreground_app() -> Option<PreviousApp> {
+    None
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn activate(_previous: PreviousApp) {}
+
+// Note the application in front before the main window takes focus
+pub fn remember(app: &AppHandle) {
+    // Showing an already focused Aura must not forget the app from before
+    if let Some(previous) = foreground_app() {
+        *app.state::<PreviousFocus>().0.lock().unwrap() = Some(previous);
+    }
+}
+
+// Drop the remembered application, e.g. when focus already moved to another one by itself
+pub fn forget(app: &AppHandle) {
+    app.state::<PreviousFocus>().0.lock().unwrap().take();
+}
+
+// Give focus back to the application that was in front before Aura was shown
+pub fn restore(app: &AppHandle) {
+    if let Some(previous) = app.state::<PreviousFocus>().0.lock().unwrap().take() {
+        activate(previous);
+    }
+}
@@ -0,0 +1,72 @@
+use serde::Serialize;
+use tauri::{AppHandle, GlobalShortcutManager};
+
+use crate::shortcut_registry;
+
+// Most shortcuts the frontend may bind at once
+const MAX_SHORTCUTS: usize = 20;
+
+// Prefix keeping frontend ids apart from the built-in ones in the shortcut registry
+const ID_PREFIX: &str = "custom:";
+
+// Action id sent to the main window when one of its shortcuts fires
+#[derive(Debug, Clone, Serialize)]
+pub struct GlobalShortcutPayload {
+    pub id: String,
+}
+
+// Bind an accelerator to an action id; an id that is already bound moves to the new keys
+#[tauri::command]
+pub fn register_global_shortcut(
+    app: AppHandle,
+    id: String,
+    accelerator: String,
+    description: Option<String>,
+) -> Result<(), String> {
+    let registry_id = format!("{}{}", ID_PREFIX, id);
+    let previous = shortcut_registry::accelerator(&app, &registry_id);
+    if previous.as_deref() == Some(accelerator.as_str()) {
+        return Ok(());
+    }
+    if previous.is_none() && shortcut_registry::count(&app, ID_PREFIX) >= MAX_SHORTCUTS {
+        return Err(format!(
+            "Cannot register more than {} shortcuts",
+            MAX_SHORTCUTS
+        ));
+    }
+
+    let already_registered = app
+        .global_shortcut_manager()
+        .is_registered(&accelerator)
+        .map_err(|err| format!("Invalid shortcut '{}': {}", accelerator, err))?;
+    if already_registered {
+        return Err(format!("Shortcut '{}' is already in use", accelerator));
+    }
+
+    let app_handle = app.clone();
+    let payload = GlobalShortcutPayload { id: id.clone() };
+    let description = description.unwrap_or_else(|| format!("Frontend action '{}'", id));
+    let result =
+        shortcut_registry::register(&app, &registry_id, &accelerator, &description, move || {
+            if let Some(window) = crate::main_window(&app_handle) {
+                window
+                    .emit("global-shortcut", payload.clone())
+                    .unwrap_or_else(|err| {
+                        tracing::error!("Failed to emit global shortcut: {}", err)
+                    });
+            }
+        });
+    // A new id that failed is not kept, so the frontend can simply try again
+    if result.is_err() && previous.is_none() {
+        shortcut_registry::unregister(&app, &registry_id);
+    }
+    result.map_err(|err| format!("Failed to register shortcut '{}': {}", accelerator, err))
+}
+
+// Release the accelerator bound to an action id
+#[tauri::command]
+pub fn unregister_global_shortcut(app: AppHandle, id: String) -> Result<(), String> {
+    shortcut_registry::unregister(&app, &format!("{}{}", ID_PREFIX, id))
+        .map(|_| ())
+        .ok_or_else(|| format!("No shortcut registered for '{}'", id))
+}
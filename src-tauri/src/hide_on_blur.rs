@@ -0,0 +1,100 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager, Window};
+
+use crate::{fade, focus_restore, settings, tray_state};
+
+// Tray menu item mirroring the hide-on-blur setting
+pub const TRAY_ITEM_ID: &str = "hide_on_blur";
+
+// Blur events this soon after a shortcut show are side effects of the show itself
+const SHOW_GRACE_PERIOD: Duration = Duration::from_millis(300);
+
+// Time allowed for focus to land on another Aura window before hiding
+const FOCUS_SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+// Opt-in behavior that hides the main window when it loses focus
+pub struct HideOnBlur {
+    enabled: Arc<AtomicBool>,
+    shown_at: Mutex<Option<Instant>>,
+}
+
+impl HideOnBlur {
+    pub fn new(enabled: bool) -> Self {
+        HideOnBlur {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+            shown_at: Mutex::new(None),
+        }
+    }
+
+    // Start ignoring blur events caused by showing the window
+    pub fn mark_shown(&self) {
+        *self.shown_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    fn in_grace_period(&self) -> bool {
+        self.shown_at
+            .lock()
+            .unwrap()
+            .map_or(false, |shown_at| shown_at.elapsed() < SHOW_GRACE_PERIOD)
+    }
+
+    // Hide the window after it loses focus, unless focus moved to another Aura window
+    pub fn handle_blur(&self, window: &Window) {
+        if !self.enabled.load(Ordering::SeqCst) || self.in_grace_period() {
+            return;
+        }
+
+        let window = window.clone();
+        thread::spawn(move || {
+            thread::sleep(FOCUS_SETTLE_DELAY);
+            if window.is_focused().unwrap_or(false) {
+                return;
+            }
+            let aura_focused = window.windows().values().any(|other| {
+                other.label() != window.label() && other.is_focused().unwrap_or(false)
+            });
+            if aura_focused {
+                return;
+            }
+            // The user picked where focus went, so it is not handed back to the earlier app
+            focus_restore::forget(&window.app_handle());
+            if let Err(err) = fade::hide(&window) {
+                tracing::error!("Failed to hide window on blur: {}", err);
+            }
+        });
+    }
+}
+
+// Whether the main window hides when it loses focus
+pub fn is_enabled(app: &AppHandle) -> bool {
+    app.state::<HideOnBlur>().enabled.load(Ordering::SeqCst)
+}
+
+// Turn hide-on-blur on or off, persist it and update the tray checkmark
+pub fn apply(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    app.state::<HideOnBlur>()
+        .enabled
+        .store(enabled, Ordering::SeqCst);
+    settings::update(app, |settings| settings.hide_on_blur = enabled)?;
+    tray_state::set_checked(app, TRAY_ITEM_ID, enabled)
+}
+
+// Enable or disable hiding the main window when it loses focus
+#[tauri::command]
+pub fn set_hide_on_blur(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply(&app, enabled)
+}
+
+// Hide the main window after it loses focus when the behavior is enabled
+pub fn on_blur(window: &Window) {
+    window.state::<HideOnBlur>().handle_blur(window);
+}
+
+// Note that the main window was just shown so the resulting blur is ignored
+pub fn mark_shown(app: &AppHandle) {
+    app.state::<HideOnBlur>().mark_shown();
+}
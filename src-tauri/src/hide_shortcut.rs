@@ -0,0 +1,125 @@
+use tauri::{AppHandle, GlobalShortcutManager};
+
+use crate::toggle_shortcut::ShortcutError;
+use crate::{fade, settings, shortcut_registry};
+
+// Default accelerator for dismissing the window; a bare Escape cannot be global
+pub const DEFAULT_HIDE_SHORTCUT: &str = "Shift+Escape";
+
+// Registry id of the hide shortcut
+const HIDE_ID: &str = "hide";
+
+// Accelerator parts that are modifiers rather than the key itself
+const MODIFIERS: [&str; 11] = [
+    "CMDORCTRL",
+    "COMMANDORCONTROL",
+    "CTRL",
+    "CONTROL",
+    "CMD",
+    "COMMAND",
+    "SUPER",
+    "META",
+    "ALT",
+    "OPTION",
+    "SHIFT",
+];
+
+// Reject accelerators the OS will not bind globally, or would take from every other
+// application: without a modifier only the function keys are allowed
+fn validate(app: &AppHandle, accelerator: &str) -> Result<(), ShortcutError> {
+    let parts: Vec<String> = accelerator
+        .split('+')
+        .map(|part| part.trim().to_uppercase())
+        .collect();
+    let has_modifier = parts.iter().any(|part| MODIFIERS.contains(&part.as_str()));
+    let is_function_key = parts.len() == 1
+        && parts[0].len() > 1
+        && parts[0].starts_with('F')
+        && parts[0][1..].parse::<u8>().is_ok();
+    if !has_modifier && !is_function_key {
+        return Err(ShortcutError::InvalidSyntax(format!(
+            "Shortcut '{}' needs a modifier such as Ctrl or Shift to work outside Aura",
+            accelerator
+        )));
+    }
+    app.global_shortcut_manager()
+        .is_registered(accelerator)
+        .map(|_| ())
+        .map_err(|err| {
+            ShortcutError::InvalidSyntax(format!("Invalid shortcut '{}': {}", accelerator, err))
+        })
+}
+
+// Hide the main window if it is shown; this shortcut never shows it
+fn hide(app: &AppHandle) {
+    let window = match crate::main_window(app) {
+        Some(window) => window,
+        None => return,
+    };
+    if fade::is_shown(&window).unwrap_or(false) {
+        fade::hide(&window).unwrap_or_else(|err| tracing::error!("Failed to hide window: {}", err));
+    }
+}
+
+fn register(app: &AppHandle, accelerator: &str) -> Result<(), String> {
+    let app_handle = app.clone();
+    shortcut_registry::register(app, HIDE_ID, accelerator, "Hide the assistant", move || {
+        hide(&app_handle)
+    })
+}
+
+// Bind the saved hide shortcut; an empty accelerator leaves it off
+pub fn register_saved(app: &AppHandle, accelerator: &str) {
+    if accelerator.is_empty() {
+        return;
+    }
+    if let Err(err) = validate(app, accelerator) {
+        tracing::warn!("Not binding the hide shortcut: {}", err);
+        return;
+    }
+    // The failure stays in the registry so the user is told to pick another binding
+    let _ = register(app, accelerator);
+}
+
+// Move the hide shortcut to a new accelerator, or turn it off when empty, and persist it
+pub fn apply(app: &AppHandle, accelerator: String) -> Result<(), ShortcutError> {
+    if accelerator.is_empty() {
+        shortcut_registry::unregister(app, HIDE_ID);
+        return settings::update(app, |settings| settings.hide_shortcut = accelerator)
+            .map_err(ShortcutError::Platform);
+    }
+
+    validate(app, &accelerator)?;
+    let current = shortcut_registry::accelerator(app, HIDE_ID);
+    let already_registered = app
+        .global_shortcut_manager()
+        .is_registered(&accelerator)
+        .unwrap_or(false);
+    if already_registered {
+        if current.as_deref() == Some(accelerator.as_str()) {
+            return Ok(());
+        }
+        return Err(ShortcutError::AlreadyTaken(format!(
+            "Shortcut '{}' is already used by another Aura action",
+            accelerator
+        )));
+    }
+
+    // The registry keeps the previous binding if another app holds the new keys
+    if let Err(err) = register(app, &accelerator) {
+        shortcut_registry::notify_failures(app);
+        return Err(ShortcutError::AlreadyTaken(format!(
+            "Shortcut '{}' is taken by another application: {}",
+            accelerator, err
+        )));
+    }
+
+    settings::update(app, |settings| settings.hide_shortcut = accelerator)
+        .map_err(ShortcutError::Platform)
+}
+
+// Change the accelerator that only hides the assistant; an empty one turns it off
+#[tauri::command]
+pub fn set_hide_shortcut(app: AppHandle, accelerator: String) -> Result<(), ShortcutError> {
+    apply(&app, accelerator)
+}
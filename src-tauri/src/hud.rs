@@ -0,0 +1,76 @@
+use serde::Serialize;
+use tauri::{AppHandle, LogicalSize, Manager, PhysicalPosition, Window, WindowBuilder, WindowUrl};
+
+use crate::monitor;
+
+// Label of the dictation status window
+pub const HUD_WINDOW: &str = "hud";
+
+// HUD size and distance from the top of the work area, in logical pixels
+const HUD_WIDTH: f64 = 240.0;
+const HUD_HEIGHT: f64 = 48.0;
+const HUD_TOP_MARGIN: f64 = 24.0;
+
+// Status text pushed to the HUD
+#[derive(Debug, Clone, Serialize)]
+pub struct HudPayload {
+    pub text: String,
+}
+
+// Create the hidden HUD window; it never takes focus and lets clicks pass through
+pub fn create(app: &AppHandle) -> tauri::Result<()> {
+    let window = WindowBuilder::new(app, HUD_WINDOW, WindowUrl::App("hud.html".into()))
+        .title("Aura Status")
+        .inner_size(HUD_WIDTH, HUD_HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .transparent(true)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .visible(false)
+        .build()?;
+    window.set_ignore_cursor_events(true)
+}
+
+// Place the HUD at the top center of the monitor the user is working on
+fn position(window: &Window) -> tauri::Result<()> {
+    let monitor =
+        match monitor::cursor_position().and_then(|cursor| monitor::monitor_at(window, cursor)) {
+            Some(monitor) => monitor,
+            None => match window.primary_monitor()? {
+                Some(monitor) => monitor,
+                None => return Ok(()),
+            },
+        };
+
+    let area = monitor::work_area(&monitor);
+    let scale_factor = monitor.scale_factor();
+    let size = LogicalSize::new(HUD_WIDTH, HUD_HEIGHT).to_physical::<u32>(scale_factor);
+    let x = area.x + (area.width as i32 - size.width as i32) / 2;
+    let y = area.y + (HUD_TOP_MARGIN * scale_factor).round() as i32;
+
+    window.set_size(size)?;
+    window.set_position(PhysicalPosition::new(x, y))
+}
+
+// Show the HUD with the given status text without taking focus from the active app
+#[tauri::command]
+pub fn show_hud(app: AppHandle, text: String) -> Result<(), String> {
+    let window = app.get_window(HUD_WINDOW).ok_or("HUD window not found")?;
+    window
+        .emit("hud-update", HudPayload { text })
+        .map_err(|err| err.to_string())?;
+    if !window.is_visible().map_err(|err| err.to_string())? {
+        position(&window).map_err(|err| err.to_string())?;
+        window.show().map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+// Hide the HUD
+#[tauri::command]
+pub fn hide_hud(app: AppHandle) -> Result<(), String> {
+    let window = app.get_window(HUD_WINDOW).ok_or("HUD window not found")?;
+    window.hide().map_err(|err| err.to_string())
+}
@@ -0,0 +1,51 @@
+use std::thread;
+use std::time::Duration;
+
+use tauri::{Manager, Window};
+use user_idle::UserIdle;
+
+use crate::{fade, pause, settings};
+
+// How often system idle time is sampled
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Hide the main window once the user has been idle longer than the configured threshold
+pub fn watch_idle(window: Window) {
+    thread::spawn(move || {
+        let mut reported_error = false;
+        loop {
+            thread::sleep(IDLE_POLL_INTERVAL);
+            if pause::is_paused(&window.app_handle()) {
+                continue;
+            }
+
+            // Read every time so changes from the settings UI apply without a restart
+            let minutes = settings::current(&window.app_handle()).auto_hide_minutes;
+            if minutes == 0 || !window.is_visible().unwrap_or(false) {
+                continue;
+            }
+
+            let idle = match UserIdle::get_time() {
+                Ok(idle) => idle,
+                Err(err) => {
+                    if !reported_error {
+                        tracing::error!("Failed to read idle time: {:?}", err);
+                        reported_error = true;
+                    }
+                    continue;
+                }
+            };
+            if idle.as_minutes() < minutes {
+                continue;
+            }
+
+            if let Err(err) = fade::hide(&window) {
+                tracing::error!("Failed to auto-hide window: {}", err);
+                continue;
+            }
+            window
+                .emit("auto-hidden", ())
+                .unwrap_or_else(|err| tracing::error!("Failed to emit auto-hidden: {}", err));
+        }
+    });
+}
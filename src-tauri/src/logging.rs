@@ -0,0 +1,54 @@
+use std::io;
+
+use tauri::{AppHandle, Config};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter};
+
+// Log files are named aura.<date>.log under app_log_dir()
+const LOG_FILE_PREFIX: &str = "aura";
+const LOG_FILE_SUFFIX: &str = "log";
+
+// Days of logs kept; older files are deleted as new ones are started
+const MAX_LOG_FILES: usize = 7;
+
+// Level used when RUST_LOG is not set
+const DEFAULT_LEVEL: &str = "info";
+
+// Send logs to a daily rotating file and to stderr; runs before the builder so setup and the
+// deep link handoff are logged too
+pub fn init(config: &Config) {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LEVEL));
+    let stderr = fmt::layer().with_writer(io::stderr);
+
+    let file = tauri::api::path::app_log_dir(config).and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(MAX_LOG_FILES)
+            .build(&dir)
+            .map_err(|err| eprintln!("Failed to open log file in '{}': {}", dir.display(), err))
+            .ok()
+    });
+    let file = file.map(|appender| fmt::layer().with_ansi(false).with_writer(appender));
+
+    if let Err(err) = tracing_subscriber::registry()
+        .with(filter)
+        .with(stderr)
+        .with(file)
+        .try_init()
+    {
+        eprintln!("Failed to set up logging: {}", err);
+    }
+}
+
+// Folder holding the log files, for attaching to bug reports
+#[tauri::command]
+pub fn get_log_path(app: AppHandle) -> Option<String> {
+    app.path_resolver()
+        .app_log_dir()
+        .map(|dir| dir.to_string_lossy().into_owned())
+}
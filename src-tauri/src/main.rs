@@ -1,93 +1,461 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod active_window;
+mod audio_devices;
+mod audit;
+mod autostart;
+mod battery_status;
+mod clipboard;
+mod close_behavior;
+mod deep_link;
+mod deletion;
+mod double_tap;
+mod external;
+mod fade;
+mod file_metadata;
+mod file_ops;
+mod file_search;
+mod file_watch;
+mod files;
+mod focus_restore;
+mod global_shortcuts;
+mod hide_on_blur;
+mod hide_shortcut;
+mod hud;
+mod idle;
+mod logging;
+mod minimize_to_tray;
+mod monitor;
+mod notifications;
+mod opacity;
+mod palette;
+mod paste;
+mod pause;
+mod peek;
+mod power;
+mod process_stats;
+mod push_to_talk;
+mod recording;
+mod reveal;
+mod screen_capture;
+mod selection;
+mod settings;
+mod settings_window;
+mod shortcut_actions;
+mod shortcut_registry;
+mod shutdown;
+mod snap;
+mod speech;
+mod taskbar;
+mod toggle_shortcut;
+mod tray_actions;
+#[cfg(target_os = "windows")]
+mod tray_badge;
+mod tray_click;
+mod tray_presence;
+mod tray_recent;
+mod tray_state;
+mod updater;
+mod window_controls;
+mod window_effect;
+mod window_mode;
+mod window_state;
+mod zoom;
+
+use std::sync::Mutex;
+
+use serde::Serialize;
 use tauri::{
-    AppHandle, CustomMenuItem, GlobalShortcutManager, Manager, SystemTray, SystemTrayEvent,
-    SystemTrayMenu, SystemTrayMenuItem, Window, WindowEvent,
+    AppHandle, CustomMenuItem, Manager, RunEvent, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem, Theme, Window, WindowEvent,
 };
+use tauri_plugin_autostart::MacosLauncher;
+
+use active_window::LastActiveWindow;
+use close_behavior::PendingClose;
+use deep_link::DeepLinks;
+use deletion::Confirmations;
+use double_tap::DoubleTap;
+use fade::Fade;
+use file_search::Searches;
+use file_watch::{FileWatches, PathWatches};
+use focus_restore::PreviousFocus;
+use hide_on_blur::HideOnBlur;
+use minimize_to_tray::MinimizedToTray;
+use pause::Paused;
+use peek::Peek;
+use process_stats::ProcessMonitor;
+use recording::Recorder;
+use settings::SettingsState;
+use shortcut_registry::{ShortcutCapture, ShortcutRegistry};
+use shutdown::Closing;
+use speech::Speech;
+use toggle_shortcut::ToggleDebounce;
+use tray_actions::{TrayAction, TrayActions};
+use tray_click::TrayClicks;
+use tray_presence::TrayPresence;
+use tray_recent::{RecentTrayItems, TrayItem};
+use tray_state::{TrayState, TrayStatus};
+use updater::PendingUpdate;
+use window_controls::LastWindowStatus;
+use window_effect::WindowEffect;
+use window_mode::{ModeAnimation, WindowMode};
+use window_state::GeometrySaver;
+
+// Label of the assistant window declared in tauri.conf.json
+const MAIN_WINDOW: &str = "main";
+
+// Arguments forwarded from a second launch of the app
+#[derive(Clone, Serialize)]
+struct SecondInstancePayload {
+    args: Vec<String>,
+    cwd: String,
+}
+
+// OS color scheme sent to the frontend
+#[derive(Clone, Serialize)]
+struct ThemePayload {
+    theme: &'static str,
+}
+
+fn theme_name(theme: Theme) -> &'static str {
+    match theme {
+        Theme::Dark => "dark",
+        _ => "light",
+    }
+}
 
-// System tray menu items
-fn create_system_tray() -> SystemTray {
+// System tray menu items under a status line; actions the frontend offers right now follow the
+// status line, recent documents and conversations go above Quit. Toggle items start checked
+// according to the settings
+fn tray_menu(
+    status: &str,
+    actions: &[TrayAction],
+    recent: &[TrayItem],
+    user_settings: &settings::Settings,
+) -> SystemTrayMenu {
+    let checked = |item: CustomMenuItem, selected: bool| {
+        if selected {
+            item.selected()
+        } else {
+            item
+        }
+    };
+    let status = CustomMenuItem::new(tray_state::STATUS_ITEM_ID.to_string(), status).disabled();
     let quit = CustomMenuItem::new("quit".to_string(), "Quit Aura");
     let show = CustomMenuItem::new("show".to_string(), "Show Assistant");
     let hide = CustomMenuItem::new("hide".to_string(), "Hide Assistant");
+    let always_on_top = checked(
+        CustomMenuItem::new("always_on_top".to_string(), "Always on Top"),
+        user_settings.always_on_top,
+    );
+    let hide_on_blur = checked(
+        CustomMenuItem::new(hide_on_blur::TRAY_ITEM_ID.to_string(), "Hide on Focus Loss"),
+        user_settings.hide_on_blur,
+    );
+    let autostart = checked(
+        CustomMenuItem::new(autostart::TRAY_ITEM_ID.to_string(), "Start at Login"),
+        user_settings.launch_at_login,
+    );
+    let skip_taskbar = checked(
+        CustomMenuItem::new(taskbar::TRAY_ITEM_ID.to_string(), taskbar::TRAY_ITEM_LABEL),
+        user_settings.skip_taskbar,
+    );
     let settings = CustomMenuItem::new("settings".to_string(), "Settings");
-    
-    let tray_menu = SystemTrayMenu::new()
+    let check_updates = CustomMenuItem::new(updater::TRAY_ITEM_ID.to_string(), "Check for Updates");
+    let repair_shortcuts = CustomMenuItem::new("repair_shortcuts".to_string(), "Repair Shortcuts");
+    let pause_hotkeys = checked(
+        CustomMenuItem::new(
+            shortcut_registry::PAUSE_TRAY_ITEM_ID.to_string(),
+            "Pause Hotkeys",
+        ),
+        user_settings.hotkeys_paused,
+    );
+    let pause_aura = CustomMenuItem::new(pause::TRAY_ITEM_ID.to_string(), "Pause Aura");
+
+    let mut tray_menu = SystemTrayMenu::new()
+        .add_item(status)
+        .add_native_item(SystemTrayMenuItem::Separator);
+
+    if !actions.is_empty() {
+        for action in actions {
+            let id = format!("{}{}", tray_actions::ID_PREFIX, action.id);
+            let item = CustomMenuItem::new(id, action.label.clone());
+            tray_menu = tray_menu.add_item(if action.enabled {
+                item
+            } else {
+                item.disabled()
+            });
+        }
+        tray_menu = tray_menu.add_native_item(SystemTrayMenuItem::Separator);
+    }
+
+    tray_menu = tray_menu
         .add_item(show)
         .add_item(hide)
         .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(always_on_top)
+        .add_item(hide_on_blur)
+        .add_item(autostart)
+        .add_item(skip_taskbar)
         .add_item(settings)
+        .add_item(repair_shortcuts)
+        .add_item(pause_hotkeys)
+        .add_item(pause_aura)
+        .add_item(check_updates);
+
+    if !recent.is_empty() {
+        tray_menu = tray_menu
+            .add_native_item(SystemTrayMenuItem::Separator)
+            .add_item(CustomMenuItem::new("recent".to_string(), "Recent").disabled());
+        for item in recent {
+            let id = format!("{}{}", tray_recent::ID_PREFIX, item.id);
+            tray_menu = tray_menu.add_item(CustomMenuItem::new(id, item.label.clone()));
+        }
+    }
+
+    tray_menu
         .add_native_item(SystemTrayMenuItem::Separator)
-        .add_item(quit);
-    
-    SystemTray::new().with_menu(tray_menu)
+        .add_item(quit)
+}
+
+fn create_system_tray(user_settings: &settings::Settings) -> SystemTray {
+    tray_click::configure_tray(SystemTray::new(), user_settings.tray_left_click_action)
+        .with_id(tray_state::TRAY_ID)
+        .with_menu(tray_menu(
+            &tray_state::status_line(TrayState::Idle),
+            &[],
+            &[],
+            user_settings,
+        ))
+}
+
+// Swap in a menu with the current frontend actions and recent entries; set_menu replaces the
+// menu without recreating the tray icon
+pub(crate) fn rebuild_tray_menu(app: &AppHandle) -> Result<(), String> {
+    let menu = tray_menu(
+        &tray_state::status_title(app),
+        &tray_actions::current(app),
+        &tray_recent::current(app),
+        &settings::current(app),
+    );
+    tray_state::tray(app)?
+        .set_menu(menu)
+        .map_err(|err| err.to_string())?;
+    // The pause is not a setting, so the new menu does not carry its checkmark
+    sync_tray_checkmarks(app);
+    Ok(())
+}
+
+// Bring the checkmarks of the toggle items in line with the settings and the pause
+pub(crate) fn sync_tray_checkmarks(app: &AppHandle) {
+    let user_settings = settings::current(app);
+    let checkmarks = [
+        ("always_on_top", user_settings.always_on_top),
+        (hide_on_blur::TRAY_ITEM_ID, user_settings.hide_on_blur),
+        (autostart::TRAY_ITEM_ID, user_settings.launch_at_login),
+        (taskbar::TRAY_ITEM_ID, user_settings.skip_taskbar),
+        (
+            shortcut_registry::PAUSE_TRAY_ITEM_ID,
+            user_settings.hotkeys_paused,
+        ),
+        (pause::TRAY_ITEM_ID, pause::is_paused(app)),
+    ];
+    for (id, selected) in checkmarks {
+        tray_state::set_checked(app, id, selected)
+            .unwrap_or_else(|err| tracing::error!("Failed to update tray menu: {}", err));
+    }
 }
 
 // Handle system tray events
 fn handle_system_tray_event(app: &AppHandle, event: SystemTrayEvent) {
     match event {
-        SystemTrayEvent::LeftClick {
-            position: _,
-            size: _,
-            ..
-        } => {
-            let window = app.get_window("main").unwrap();
-            if window.is_visible().unwrap() {
-                window.hide().unwrap();
-            } else {
-                window.show().unwrap();
-                window.set_focus().unwrap();
-            }
-        }
+        SystemTrayEvent::LeftClick { .. } => tray_click::on_left_click(app),
+        SystemTrayEvent::DoubleClick { .. } => tray_click::on_double_click(app),
         SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
-            "quit" => {
-                std::process::exit(0);
-            }
+            "quit" => shutdown::quit(app),
             "show" => {
-                let window = app.get_window("main").unwrap();
-                window.show().unwrap();
-                window.set_focus().unwrap();
+                if let Some(window) = main_window(app) {
+                    monitor::prepare_to_show(&window);
+                    if let Err(err) = fade::show(&window) {
+                        tracing::error!("Failed to show window: {}", err);
+                    }
+                }
             }
             "hide" => {
-                let window = app.get_window("main").unwrap();
-                window.hide().unwrap();
+                if let Some(window) = main_window(app) {
+                    if let Err(err) = fade::hide(&window) {
+                        tracing::error!("Failed to hide window: {}", err);
+                    }
+                }
+            }
+            "always_on_top" => {
+                let enabled = !settings::current(app).always_on_top;
+                if let Err(err) = apply_always_on_top(app, enabled) {
+                    tracing::error!("Failed to toggle always on top: {}", err);
+                }
+            }
+            hide_on_blur::TRAY_ITEM_ID => {
+                let enabled = !hide_on_blur::is_enabled(app);
+                if let Err(err) = hide_on_blur::apply(app, enabled) {
+                    tracing::error!("Failed to toggle hide on focus loss: {}", err);
+                }
+            }
+            autostart::TRAY_ITEM_ID => {
+                let enabled = !autostart::is_enabled(app);
+                if let Err(err) = autostart::apply(app, enabled) {
+                    tracing::error!("Failed to toggle start at login: {}", err);
+                }
+            }
+            taskbar::TRAY_ITEM_ID => {
+                let enabled = !settings::current(app).skip_taskbar;
+                if let Err(err) = taskbar::apply(app, enabled) {
+                    tracing::error!("Failed to toggle taskbar entry: {}", err);
+                }
             }
             "settings" => {
-                let window = app.get_window("main").unwrap();
-                window.show().unwrap();
-                window.set_focus().unwrap();
-                // Emit event to show settings modal
-                window.emit("show-settings", {}).unwrap();
+                if let Err(err) = settings_window::open(app) {
+                    tracing::error!("Failed to open settings: {}", err);
+                }
+            }
+            "repair_shortcuts" => shortcut_registry::refresh(app),
+            updater::TRAY_ITEM_ID => updater::check_in_background(app),
+            shortcut_registry::PAUSE_TRAY_ITEM_ID => {
+                let paused = !shortcut_registry::is_paused(app);
+                if let Err(err) = shortcut_registry::set_paused(app, paused) {
+                    tracing::error!("Failed to toggle hotkey pause: {}", err);
+                }
             }
-            _ => {}
+            pause::TRAY_ITEM_ID => {
+                let paused = !pause::is_paused(app);
+                if let Err(err) = pause::apply(app, paused) {
+                    tracing::error!("Failed to toggle pause: {}", err);
+                }
+            }
+            id if id.starts_with(tray_actions::ID_PREFIX) => tray_actions::on_menu_click(app, id),
+            id => tray_recent::on_menu_click(app, id),
         },
         _ => {}
     }
 }
 
+// Look up the main window, logging when it no longer exists
+pub(crate) fn main_window(app: &AppHandle) -> Option<Window> {
+    let window = app.get_window(MAIN_WINDOW);
+    if window.is_none() {
+        tracing::warn!("Main window not found");
+    }
+    window
+}
+
+// Show a window and give it keyboard focus
+pub(crate) fn show_and_focus(window: &Window) -> tauri::Result<()> {
+    focus_restore::remember(&window.app_handle());
+    window.show()?;
+    minimize_to_tray::restore(window)?;
+    window.set_focus()
+}
+
+// Hide a window and hand focus back to the app the user was in before
+pub(crate) fn hide_and_restore_focus(window: &Window) -> tauri::Result<()> {
+    window.hide()?;
+    focus_restore::restore(&window.app_handle());
+    Ok(())
+}
+
+// Pin the main window above other windows and keep the tray, settings and frontend in sync
+pub(crate) fn apply_always_on_top(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let window = main_window(app).ok_or("Main window not found")?;
+    window
+        .set_always_on_top(enabled)
+        .map_err(|err| err.to_string())?;
+    tray_state::set_checked(app, "always_on_top", enabled)?;
+    settings::update(app, |settings| settings.always_on_top = enabled)?;
+
+    window_controls::notify_changed(&window);
+
+    // Let the frontend update its pin indicator regardless of where the change came from
+    app.emit_all("always-on-top-changed", enabled)
+        .map_err(|err| err.to_string())
+}
+
+// Toggle the main window from a global shortcut or the tray icon
+pub(crate) fn toggle_main_window(app: &AppHandle) {
+    let window = match main_window(app) {
+        Some(window) => window,
+        None => return,
+    };
+    let visible = match fade::is_shown(&window) {
+        Ok(visible) => visible,
+        Err(err) => {
+            tracing::error!("Failed to read window visibility: {}", err);
+            return;
+        }
+    };
+
+    let result = if visible {
+        fade::hide(&window)
+    } else {
+        monitor::prepare_to_show(&window);
+        hide_on_blur::mark_shown(app);
+        fade::show(&window)
+    };
+    if let Err(err) = result {
+        tracing::error!("Failed to toggle window: {}", err);
+    }
+}
+
+// Replace the toggle shortcut with a new accelerator; the original name of set_toggle_shortcut
+#[tauri::command]
+fn set_global_shortcut(app: AppHandle, new_accelerator: String) -> Result<(), String> {
+    toggle_shortcut::apply(&app, new_accelerator).map_err(|err| err.to_string())
+}
+
 // Toggle window visibility
 #[tauri::command]
-fn toggle_window(window: Window) {
-    if window.is_visible().unwrap() {
-        window.hide().unwrap();
+fn toggle_window(window: Window) -> Result<(), String> {
+    let result = if fade::is_shown(&window).map_err(|err| err.to_string())? {
+        fade::hide(&window)
     } else {
-        window.show().unwrap();
-        window.set_focus().unwrap();
-    }
+        fade::show(&window)
+    };
+    result.map_err(|err| err.to_string())
 }
 
 // Show window
 #[tauri::command]
-fn show_window(window: Window) {
-    window.show().unwrap();
-    window.set_focus().unwrap();
+fn show_window(window: Window) -> Result<(), String> {
+    monitor::prepare_to_show(&window);
+    fade::show(&window).map_err(|err| err.to_string())
+}
+
+// Show window centered near the top of the monitor under the cursor
+#[tauri::command]
+fn show_window_at_cursor(window: Window) -> Result<(), String> {
+    monitor::position_at_cursor(&window).map_err(|err| err.to_string())?;
+    fade::show(&window).map_err(|err| err.to_string())
 }
 
 // Hide window
 #[tauri::command]
-fn hide_window(window: Window) {
-    window.hide().unwrap();
+fn hide_window(window: Window) -> Result<(), String> {
+    fade::hide(&window).map_err(|err| err.to_string())
+}
+
+// Keep the main window above other windows
+#[tauri::command]
+fn set_always_on_top(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply_always_on_top(&app, enabled)
+}
+
+// Get the current OS color scheme
+#[tauri::command]
+fn get_system_theme(window: Window) -> Result<String, String> {
+    window
+        .theme()
+        .map(|theme| theme_name(theme).to_string())
+        .map_err(|err| err.to_string())
 }
 
 // Get system information
@@ -117,86 +485,402 @@ fn get_app_data_dir(app: AppHandle) -> Option<String> {
 
 // Get documents directory
 #[tauri::command]
-fn get_documents_dir(app: AppHandle) -> Option<String> {
-    app.path_resolver()
-        .document_dir()
-        .map(|path| path.to_string_lossy().to_string())
+fn get_documents_dir() -> Option<String> {
+    tauri::api::path::document_dir().map(|path| path.to_string_lossy().to_string())
+}
+
+// Create an app directory on first use; None when it cannot be created
+fn ensure_dir(path: Option<std::path::PathBuf>) -> Option<String> {
+    let path = path?;
+    if let Err(err) = std::fs::create_dir_all(&path) {
+        tracing::error!("Failed to create '{}': {}", path.display(), err);
+        return None;
+    }
+    Some(path.to_string_lossy().to_string())
+}
+
+// Get app cache directory, where recordings are kept
+#[tauri::command]
+fn get_cache_dir(app: AppHandle) -> Option<String> {
+    ensure_dir(app.path_resolver().app_cache_dir())
+}
+
+// Get app config directory
+#[tauri::command]
+fn get_config_dir(app: AppHandle) -> Option<String> {
+    ensure_dir(app.path_resolver().app_config_dir())
 }
 
 fn main() {
+    let context = tauri::generate_context!();
+    logging::init(context.config());
+
+    shutdown::wait_for_previous_instance();
+    deep_link::prepare();
+
     tauri::Builder::default()
-        .system_tray(create_system_tray())
+        // A second launch focuses the running instance and exits instead of grabbing the shortcuts
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            if let Some(window) = main_window(app) {
+                show_and_focus(&window).unwrap_or_else(|err| {
+                    tracing::error!("Failed to show window: {}", err);
+                });
+            }
+            app.emit_all("second-instance", SecondInstancePayload { args, cwd })
+                .unwrap_or_else(|err| {
+                    tracing::error!("Failed to forward second instance arguments: {}", err);
+                });
+        }))
+        .plugin(tauri_plugin_autostart::init(
+            MacosLauncher::LaunchAgent,
+            None,
+        ))
         .on_system_tray_event(handle_system_tray_event)
         .invoke_handler(tauri::generate_handler![
             toggle_window,
             show_window,
+            show_window_at_cursor,
             hide_window,
             get_system_info,
+            get_system_theme,
             file_exists,
+            file_metadata::file_metadata,
+            files::read_text_file,
+            files::write_text_file,
+            files::list_directory,
+            files::trash_file,
+            deletion::delete_to_trash,
+            deletion::request_destructive_confirmation,
+            deletion::delete_permanently,
+            file_ops::copy_file,
+            file_ops::move_file,
+            file_search::glob_search,
+            file_search::cancel_search,
+            file_watch::watch_file,
+            file_watch::unwatch_file,
+            file_watch::watch_path,
+            file_watch::unwatch_path,
             get_app_data_dir,
-            get_documents_dir
+            get_documents_dir,
+            get_cache_dir,
+            get_config_dir,
+            set_global_shortcut,
+            toggle_shortcut::set_toggle_shortcut,
+            hide_shortcut::set_hide_shortcut,
+            global_shortcuts::register_global_shortcut,
+            global_shortcuts::unregister_global_shortcut,
+            shortcut_registry::get_shortcut_status,
+            shortcut_registry::list_registered_shortcuts,
+            shortcut_registry::reregister_shortcuts,
+            shortcut_registry::begin_shortcut_capture,
+            shortcut_registry::end_shortcut_capture,
+            shortcut_registry::set_shortcut_enabled,
+            shortcut_registry::set_all_shortcuts_enabled,
+            double_tap::set_double_tap_activation,
+            deep_link::deep_link_ready,
+            shutdown::acknowledge_closing,
+            shutdown::restart_app,
+            battery_status::get_battery_status,
+            speech::speak,
+            speech::stop_speaking,
+            speech::list_tts_voices,
+            recording::start_recording,
+            recording::stop_recording,
+            logging::get_log_path,
+            tray_recent::update_tray_menu,
+            tray_actions::set_tray_actions,
+            tray_state::set_tray_state,
+            tray_state::set_tray_icon,
+            tray_state::set_tray_tooltip,
+            tray_state::set_tray_badge,
+            tray_presence::get_tray_status,
+            updater::check_for_updates,
+            updater::install_update,
+            paste::paste_to_active_app,
+            pause::set_paused,
+            pause::get_paused,
+            active_window::get_active_window,
+            audio_devices::list_audio_devices,
+            audio_devices::set_input_device,
+            set_always_on_top,
+            opacity::set_window_opacity,
+            hide_on_blur::set_hide_on_blur,
+            clipboard::read_clipboard,
+            clipboard::write_clipboard,
+            clipboard::read_clipboard_image,
+            clipboard::write_clipboard_image,
+            window_mode::set_window_mode,
+            autostart::set_autostart,
+            autostart::get_autostart,
+            taskbar::set_skip_taskbar,
+            snap::snap_window_to_corner,
+            snap::set_snap_shortcuts,
+            monitor::get_monitors,
+            monitor::get_current_monitor,
+            monitor::move_window_to_monitor,
+            settings_window::open_settings,
+            notifications::show_notification,
+            hud::show_hud,
+            hud::hide_hud,
+            window_controls::toggle_maximize,
+            window_controls::set_fullscreen,
+            window_controls::get_window_state,
+            window_controls::start_dragging,
+            window_controls::start_drag,
+            window_controls::set_decorations,
+            window_controls::set_position,
+            window_controls::request_attention,
+            window_effect::set_window_effect,
+            window_effect::get_window_effects,
+            settings::get_settings,
+            settings::update_settings,
+            push_to_talk::set_ptt_key,
+            push_to_talk::set_ptt_enabled,
+            close_behavior::confirm_close,
+            close_behavior::set_close_behavior,
+            screen_capture::capture_screen,
+            minimize_to_tray::set_minimize_to_tray,
+            process_stats::get_process_stats,
+            external::open_external,
+            external::open_path,
+            external::open_with,
+            reveal::reveal_in_file_manager,
+            zoom::set_zoom,
+            zoom::get_zoom,
+            peek::peek_window,
+            peek::cancel_peek,
+            window_state::reset_window_geometry
         ])
+        .manage(ModeAnimation::default())
+        .manage(LastWindowStatus::default())
+        .manage(PendingClose::default())
+        .manage(MinimizedToTray::default())
+        .manage(PreviousFocus::default())
+        .manage(Fade::default())
+        .manage(ProcessMonitor::default())
+        .manage(Peek::default())
+        .manage(ShortcutRegistry::default())
+        .manage(ShortcutCapture::default())
+        .manage(DeepLinks::default())
+        .manage(Closing::default())
+        .manage(Speech::default())
+        .manage(Recorder::default())
+        .manage(Paused::default())
+        .manage(LastActiveWindow::default())
+        .manage(ToggleDebounce::default())
+        .manage(RecentTrayItems::default())
+        .manage(TrayActions::default())
+        .manage(TrayClicks::default())
+        .manage(PendingUpdate::default())
+        .manage(TrayPresence::default())
+        .manage(TrayStatus::default())
+        .manage(Confirmations::default())
+        .manage(FileWatches::default())
+        .manage(PathWatches::default())
+        .manage(Searches::default())
         .setup(|app| {
-            // Register global shortcut
-            let mut shortcut_manager = app.global_shortcut_manager();
-            
-            // Register Ctrl+' (Ctrl+Quote) as the global shortcut
-            shortcut_manager
-                .register("CmdOrCtrl+'", {
-                    let app_handle = app.handle();
-                    move || {
-                        let window = app_handle.get_window("main").unwrap();
-                        if window.is_visible().unwrap() {
-                            window.hide().unwrap();
-                        } else {
-                            window.show().unwrap();
-                            window.set_focus().unwrap();
-                        }
-                    }
-                })
-                .unwrap_or_else(|err| {
-                    eprintln!("Failed to register global shortcut: {}", err);
+            let app_handle = app.handle();
+
+            // Load persisted preferences
+            let user_settings = settings::load(&app_handle);
+            app.manage(SettingsState(Mutex::new(user_settings.clone())));
+            app.manage(HideOnBlur::new(user_settings.hide_on_blur));
+            app.manage(DoubleTap::new(user_settings.double_tap_activation.clone()));
+
+            // Built here rather than on the builder so a desktop without a tray leaves a
+            // window-only app instead of failing to start
+            tray_presence::create(&app_handle, create_system_tray(&user_settings));
+
+            // Taskbar entry or Dock icon as the user left it, kept when there is no tray
+            taskbar::restore(&app_handle, user_settings.skip_taskbar);
+
+            // Hotkeys paused from the tray stay paused across restarts
+            shortcut_registry::restore_paused(&app_handle, user_settings.hotkeys_paused);
+
+            // Bind the saved toggle shortcut, Ctrl+' unless the user picked another
+            toggle_shortcut::register_saved(&app_handle, &user_settings.toggle_shortcut);
+
+            // Optional shortcut that only ever hides the window, Shift+Escape by default
+            hide_shortcut::register_saved(&app_handle, &user_settings.hide_shortcut);
+
+            // Name the toggle shortcut in the tray tooltip; the tray may not exist on Linux
+            tray_state::refresh_tooltip(&app_handle);
+
+            // Dictation, screenshot and clipboard capture actions
+            shortcut_actions::register(&app_handle, &user_settings.action_shortcuts);
+
+            // Quick command palette, independent of the main window
+            palette::register(&app_handle);
+
+            // Optional Ctrl+Alt+Arrow shortcuts for docking the window into a corner
+            if user_settings.snap_shortcuts {
+                snap::register_shortcuts(&app_handle);
+            }
+
+            // aura:// links from other apps, including the one this launch was opened with
+            deep_link::register(&app_handle);
+
+            // Show long-running task progress when hovering the tray icon
+            tray_state::watch_task_progress(&app_handle);
+
+            // Replace the app icon the tray was created with by the idle state icon
+            tray_state::reset(&app_handle);
+
+            // Tell the frontend about sleep and wake so it can reconnect
+            power::watch_power_events(&app_handle);
+
+            // Let the frontend slow its background work while on battery
+            battery_status::watch_battery(&app_handle);
+
+            // Hold-to-talk hotkey for voice input
+            push_to_talk::start(
+                &app_handle,
+                &user_settings.push_to_talk_shortcut,
+                user_settings.push_to_talk_enabled,
+            );
+
+            // Dictation status pill, hidden until voice input starts
+            hud::create(&app_handle)
+                .unwrap_or_else(|err| tracing::error!("Failed to create HUD window: {}", err));
+
+            // Set up window event handlers
+            let window = main_window(&app_handle).ok_or("Main window not found")?;
+
+            // Tell the user about hotkeys another app already owns; get_shortcut_status covers
+            // listeners attached after this point
+            shortcut_registry::notify_failures(&app_handle);
+
+            // Screen lock swallows the push-to-talk key-up, so it ends the utterance instead; an
+            // Explorer restart on Windows re-binds the shortcuts
+            power::watch_session_events(&window)
+                .unwrap_or_else(|err| tracing::error!("Failed to watch session events: {}", err));
+
+            let event_window = window.clone();
+
+            // Add the native frame before the saved geometry is applied and the window first
+            // shown, so it never appears borderless first
+            if user_settings.decorations {
+                window_controls::set_frame(&window, true).unwrap_or_else(|err| {
+                    tracing::error!("Failed to restore window decorations: {}", err)
                 });
+            }
 
-            // Alternative shortcut: Ctrl+Shift+A
-            shortcut_manager
-                .register("CmdOrCtrl+Shift+A", {
-                    let app_handle = app.handle();
-                    move || {
-                        let window = app_handle.get_window("main").unwrap();
-                        if window.is_visible().unwrap() {
-                            window.hide().unwrap();
-                        } else {
-                            window.show().unwrap();
-                            window.set_focus().unwrap();
-                        }
-                    }
+            // Restore the last saved geometry and display before the window is first shown
+            window_state::restore_geometry(&window);
+            monitor::restore_preferred_monitor(&window).unwrap_or_else(|err| {
+                tracing::error!("Failed to restore preferred monitor: {}", err)
+            });
+
+            // Keep the frontend's display list current as monitors come and go
+            monitor::watch_monitors(window.clone());
+
+            // Tuck the assistant away after the configured period of user inactivity
+            idle::watch_idle(window.clone());
+
+            // Re-apply the persisted always-on-top preference
+            window
+                .set_always_on_top(user_settings.always_on_top)
+                .unwrap_or_else(|err| tracing::error!("Failed to set always on top: {}", err));
+
+            // Launch at login can also be turned off from the OS, so the registration wins over
+            // the stored preference
+            let launch_at_login = autostart::is_enabled(&app_handle);
+            if launch_at_login != user_settings.launch_at_login {
+                settings::update(&app_handle, |settings| {
+                    settings.launch_at_login = launch_at_login
                 })
-                .unwrap_or_else(|err| {
-                    eprintln!("Failed to register alternative global shortcut: {}", err);
+                .unwrap_or_else(|err| tracing::error!("Failed to save settings: {}", err));
+            }
+            sync_tray_checkmarks(&app_handle);
+
+            // The settings window changes the same toggles through update_settings
+            let settings_handle = app_handle.clone();
+            app.listen_global("settings-changed", move |_| {
+                sync_tray_checkmarks(&settings_handle)
+            });
+
+            // Reopen in the last used mode; the saved geometry already holds its size
+            if user_settings.window_mode == WindowMode::Compact {
+                window_mode::apply_min_size(&window, WindowMode::Compact).unwrap_or_else(|err| {
+                    tracing::error!("Failed to restore compact mode: {}", err)
                 });
+            }
+
+            // Re-apply a persisted translucent window
+            if user_settings.window_opacity < opacity::MAX_OPACITY {
+                if let Err(err) = opacity::apply(&window, user_settings.window_opacity) {
+                    tracing::error!("Failed to restore window opacity: {:?}", err);
+                }
+            }
+
+            // Re-apply the persisted background material
+            if user_settings.window_effect != WindowEffect::None {
+                if let Err(err) = window_effect::apply(&window, user_settings.window_effect) {
+                    tracing::error!("Failed to restore window effect: {:?}", err);
+                }
+            }
+
+            // Re-apply the persisted zoom before the page is first seen
+            if user_settings.zoom != 1.0 {
+                let factor = user_settings.zoom.clamp(zoom::MIN_ZOOM, zoom::MAX_ZOOM);
+                zoom::apply(&window, factor)
+                    .unwrap_or_else(|err| tracing::error!("Failed to restore zoom: {}", err));
+            }
+
+            let geometry_saver = GeometrySaver::new(&window);
 
-            // Set up window event handlers
-            let window = app.get_window("main").unwrap();
-            
             // Handle window events
             window.on_window_event(move |event| match event {
                 WindowEvent::CloseRequested { api, .. } => {
-                    // Prevent window from closing, hide it instead
+                    // Keep the window alive and let the user's close preference decide
                     api.prevent_close();
-                    let window = api.window();
-                    window.hide().unwrap();
+                    close_behavior::on_close_requested(&event_window);
+                }
+                WindowEvent::Focused(focused) => {
+                    // Hide window when it loses focus if the user opted in; focusing a
+                    // peeking window keeps it open. Losing focus also ends push-to-talk so
+                    // recording never sticks on
+                    if *focused {
+                        peek::cancel(&event_window.app_handle());
+                        // Coming back to Aura clears a stale listening or error icon
+                        tray_state::reset(&event_window.app_handle());
+                    } else {
+                        push_to_talk::release(&event_window.app_handle());
+                        hide_on_blur::on_blur(&event_window);
+                    }
+                    zoom::on_focus_changed(&event_window.app_handle(), *focused);
+                    window_controls::notify_changed(&event_window);
                 }
-                WindowEvent::Focused(false) => {
-                    // Optionally hide window when it loses focus
-                    // Uncomment the next line if you want this behavior
-                    // api.window().hide().unwrap();
+                WindowEvent::ThemeChanged(theme) => {
+                    // Let the frontend follow the OS light/dark switch
+                    let payload = ThemePayload {
+                        theme: theme_name(*theme),
+                    };
+                    event_window
+                        .emit("theme-changed", payload)
+                        .unwrap_or_else(|err| {
+                            tracing::error!("Failed to emit theme change: {}", err);
+                        });
+                }
+                WindowEvent::Moved(_) => {
+                    geometry_saver.schedule();
+                }
+                WindowEvent::Resized(_) => {
+                    // Minimize, maximize and fullscreen changes all arrive as resizes
+                    minimize_to_tray::on_resized(&event_window);
+                    geometry_saver.schedule();
+                    window_controls::notify_changed(&event_window);
                 }
                 _ => {}
             });
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
\ No newline at end of file
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let RunEvent::Exit = event {
+                shutdown::cleanup(app_handle);
+            }
+        });
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::{AppHandle, Manager, Window};
+
+use crate::{settings, tray_presence};
+
+// Set while the main window is hidden because it was minimized to the tray
+#[derive(Default)]
+pub struct MinimizedToTray(AtomicBool);
+
+// Hide the window instead of leaving it minimized when the user opted in
+pub fn on_resized(window: &Window) {
+    let app = window.app_handle();
+    // Without a tray the taskbar entry is the only way back, so the window stays minimized
+    if !settings::current(&app).minimize_to_tray
+        || !tray_presence::is_available(&app)
+        || !window.is_minimized().unwrap_or(false)
+    {
+        return;
+    }
+
+    // The window stays minimized while hidden; restore() brings it back on the next show
+    match window.hide() {
+        Ok(()) => app
+            .state::<MinimizedToTray>()
+            .0
+            .store(true, Ordering::SeqCst),
+        Err(err) => tracing::error!("Failed to minimize to tray: {}", err),
+    }
+}
+
+// Un-minimize a window that is being shown and tell the frontend to refocus its input
+pub fn restore(window: &Window) -> tauri::Result<()> {
+    let minimized = window.is_minimized()?;
+    if minimized {
+        window.unminimize()?;
+    }
+    let from_tray = window
+        .app_handle()
+        .state::<MinimizedToTray>()
+        .0
+        .swap(false, Ordering::SeqCst);
+    if minimized || from_tray {
+        window.emit("window-restored", ())?;
+    }
+    Ok(())
+}
+
+// Enable or disable hiding the main window to the tray when it is minimized
+#[tauri::command]
+pub fn set_minimize_to_tray(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update(&app, |settings| settings.minimize_to_tray = enabled)
+}
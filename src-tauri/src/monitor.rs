@@ -0,0 +1,418 @@
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize, Window};
+
+use crate::{pause, settings};
+
+// Fraction of the work area height left above the window when shown at the cursor
+const TOP_OFFSET_RATIO: f64 = 0.15;
+
+// How often the display topology is checked for added or removed monitors
+const MONITOR_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// Rectangle in physical screen coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    pub fn from_monitor(monitor: &Monitor) -> Self {
+        let position = monitor.position();
+        let size = monitor.size();
+        Rect {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.x + other.width as i32
+            && other.x < self.x + self.width as i32
+            && self.y < other.y + other.height as i32
+            && other.y < self.y + self.height as i32
+    }
+
+    pub fn contains(&self, point: PhysicalPosition<i32>) -> bool {
+        point.x >= self.x
+            && point.x < self.x + self.width as i32
+            && point.y >= self.y
+            && point.y < self.y + self.height as i32
+    }
+}
+
+// Current cursor position in physical screen coordinates
+#[cfg(target_os = "windows")]
+pub fn cursor_position() -> Option<PhysicalPosition<i32>> {
+    use windows_sys::Win32::Foundation::POINT;
+    use windows_sys::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+    let mut point = POINT { x: 0, y: 0 };
+    if unsafe { GetCursorPos(&mut point) } == 0 {
+        return None;
+    }
+    Some(PhysicalPosition::new(point.x, point.y))
+}
+
+// Current cursor position in physical screen coordinates
+#[cfg(target_os = "macos")]
+pub fn cursor_position() -> Option<PhysicalPosition<i32>> {
+    use cocoa::appkit::{NSEvent, NSScreen};
+    use cocoa::base::nil;
+    use cocoa::foundation::NSArray;
+
+    unsafe {
+        // Cocoa uses points with the origin at the bottom-left of the primary screen
+        let location = NSEvent::mouseLocation(nil);
+        let screens = NSScreen::screens(nil);
+        if screens.count() == 0 {
+            return None;
+        }
+        let primary_height = NSScreen::frame(screens.objectAtIndex(0)).size.height;
+
+        for index in 0..screens.count() {
+            let screen = screens.objectAtIndex(index);
+            let frame = NSScreen::frame(screen);
+            if location.x >= frame.origin.x
+                && location.x < frame.origin.x + frame.size.width
+                && location.y >= frame.origin.y
+                && location.y < frame.origin.y + frame.size.height
+            {
+                let scale = NSScreen::backingScaleFactor(screen);
+                return Some(PhysicalPosition::new(
+                    (location.x * scale) as i32,
+                    ((primary_height - location.y) * scale) as i32,
+                ));
+            }
+        }
+        None
+    }
+}
+
+// Cursor lookup is not implemented on this platform
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn cursor_position() -> Option<PhysicalPosition<i32>> {
+    None
+}
+
+// Area of the monitor not covered by the taskbar, dock or menu bar
+#[cfg(target_os = "windows")]
+pub fn work_area(monitor: &Monitor) -> Rect {
+    use windows_sys::Win32::Foundation::POINT;
+    use windows_sys::Win32::Graphics::Gdi::{
+        GetMonitorInfoW, MonitorFromPoint, MONITORINFO, MONITOR_DEFAULTTONULL,
+    };
+
+    let bounds = Rect::from_monitor(monitor);
+    let center = POINT {
+        x: bounds.x + bounds.width as i32 / 2,
+        y: bounds.y + bounds.height as i32 / 2,
+    };
+
+    unsafe {
+        let handle = MonitorFromPoint(center, MONITOR_DEFAULTTONULL);
+        if handle == 0 {
+            return bounds;
+        }
+
+        let mut info: MONITORINFO = std::mem::zeroed();
+        info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+        if GetMonitorInfoW(handle, &mut info) == 0 {
+            return bounds;
+        }
+
+        let work = info.rcWork;
+        Rect {
+            x: work.left,
+            y: work.top,
+            width: (work.right - work.left) as u32,
+            height: (work.bottom - work.top) as u32,
+        }
+    }
+}
+
+// Area of the monitor not covered by the taskbar, dock or menu bar
+#[cfg(target_os = "macos")]
+pub fn work_area(monitor: &Monitor) -> Rect {
+    use cocoa::appkit::NSScreen;
+    use cocoa::base::nil;
+    use cocoa::foundation::NSArray;
+
+    let bounds = Rect::from_monitor(monitor);
+
+    unsafe {
+        let screens = NSScreen::screens(nil);
+        if screens.count() == 0 {
+            return bounds;
+        }
+        let primary_height = NSScreen::frame(screens.objectAtIndex(0)).size.height;
+
+        for index in 0..screens.count() {
+            let screen = screens.objectAtIndex(index);
+            let scale = NSScreen::backingScaleFactor(screen);
+            let frame = NSScreen::frame(screen);
+            let x = (frame.origin.x * scale) as i32;
+            let y = ((primary_height - frame.origin.y - frame.size.height) * scale) as i32;
+            if (x - bounds.x).abs() > 1 || (y - bounds.y).abs() > 1 {
+                continue;
+            }
+
+            let visible = NSScreen::visibleFrame(screen);
+            return Rect {
+                x: (visible.origin.x * scale) as i32,
+                y: ((primary_height - visible.origin.y - visible.size.height) * scale) as i32,
+                width: (visible.size.width * scale) as u32,
+                height: (visible.size.height * scale) as u32,
+            };
+        }
+        bounds
+    }
+}
+
+// Work area lookup is not implemented on this platform, use the full monitor bounds
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn work_area(monitor: &Monitor) -> Rect {
+    Rect::from_monitor(monitor)
+}
+
+// Monitor containing the given point, if any
+pub fn monitor_at(window: &Window, point: PhysicalPosition<i32>) -> Option<Monitor> {
+    window
+        .available_monitors()
+        .ok()?
+        .into_iter()
+        .find(|monitor| Rect::from_monitor(monitor).contains(point))
+}
+
+// Check whether the rectangle overlaps any connected monitor
+pub fn is_on_screen(window: &Window, rect: &Rect) -> bool {
+    window.available_monitors().map_or(false, |monitors| {
+        monitors
+            .iter()
+            .any(|monitor| Rect::from_monitor(monitor).intersects(rect))
+    })
+}
+
+// Center the window horizontally in the monitor work area, either vertically centered or
+// offset from the top by the given fraction of the work area height
+fn place_on_monitor(
+    window: &Window,
+    monitor: &Monitor,
+    top_offset_ratio: Option<f64>,
+) -> tauri::Result<()> {
+    let area = work_area(monitor);
+
+    // Convert the size to the target monitor's scale so mixed-DPI setups keep the same logical size
+    let size = window.outer_size()?;
+    let ratio = monitor.scale_factor() / window.scale_factor()?;
+    let scaled_width = (size.width as f64 * ratio) as u32;
+    let scaled_height = (size.height as f64 * ratio) as u32;
+    let width = scaled_width.min(area.width);
+    let height = scaled_height.min(area.height);
+
+    let free_height = (area.height - height) as i32;
+    let x = area.x + (area.width - width) as i32 / 2;
+    let y = area.y
+        + match top_offset_ratio {
+            Some(ratio) => ((area.height as f64 * ratio) as i32).min(free_height),
+            None => free_height / 2,
+        };
+
+    window.set_position(PhysicalPosition::new(x, y))?;
+    if width < scaled_width || height < scaled_height {
+        window.set_size(PhysicalSize::new(width, height))?;
+    }
+    Ok(())
+}
+
+// Move the window near the top of the work area of the monitor under the cursor
+pub fn position_at_cursor(window: &Window) -> tauri::Result<()> {
+    match cursor_position().and_then(|cursor| monitor_at(window, cursor)) {
+        Some(monitor) => place_on_monitor(window, &monitor, Some(TOP_OFFSET_RATIO)),
+        None => Ok(()),
+    }
+}
+
+// Center the window in the work area of the monitor under the cursor
+pub fn center_at_cursor(window: &Window) -> tauri::Result<()> {
+    match cursor_position().and_then(|cursor| monitor_at(window, cursor)) {
+        Some(monitor) => place_on_monitor(window, &monitor, None),
+        None => window.center(),
+    }
+}
+
+// Bring the window onto the monitor under the cursor when it is off-screen or the user asked
+// for it to follow the cursor's monitor, so every entry point shows it in the same place. A
+// window already on that monitor keeps its remembered position
+pub fn prepare_to_show(window: &Window) {
+    let follow_cursor = settings::current(&window.app_handle()).follow_cursor_monitor;
+    let on_screen = match (window.outer_position(), window.outer_size()) {
+        (Ok(position), Ok(size)) => is_on_screen(
+            window,
+            &Rect {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+            },
+        ),
+        _ => false,
+    };
+    if on_screen && !follow_cursor {
+        return;
+    }
+
+    let cursor_monitor = cursor_position().and_then(|cursor| monitor_at(window, cursor));
+    let result = match cursor_monitor {
+        Some(monitor) => {
+            let current = window.current_monitor().ok().flatten();
+            if on_screen && current.map_or(false, |current| is_same_monitor(&current, &monitor)) {
+                return;
+            }
+            place_on_monitor(window, &monitor, None)
+        }
+        // Without a cursor position the last saved position stays, unless it is off-screen
+        None if on_screen => return,
+        None => window.center(),
+    };
+    if let Err(err) = result {
+        tracing::error!("Failed to position window: {}", err);
+    }
+}
+
+// Display details sent to the frontend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    pub position: PhysicalPosition<i32>,
+    pub size: PhysicalSize<u32>,
+    pub work_area: Rect,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
+fn is_same_monitor(a: &Monitor, b: &Monitor) -> bool {
+    a.name() == b.name() && a.position() == b.position()
+}
+
+fn monitor_info(monitor: &Monitor, primary: Option<&Monitor>) -> MonitorInfo {
+    MonitorInfo {
+        name: monitor.name().cloned(),
+        position: *monitor.position(),
+        size: *monitor.size(),
+        work_area: work_area(monitor),
+        scale_factor: monitor.scale_factor(),
+        is_primary: primary.map_or(false, |primary| is_same_monitor(monitor, primary)),
+    }
+}
+
+// Describe every connected monitor, in the order used by move_window_to_monitor
+pub fn list_monitors(window: &Window) -> tauri::Result<Vec<MonitorInfo>> {
+    let primary = window.primary_monitor()?;
+    Ok(window
+        .available_monitors()?
+        .iter()
+        .map(|monitor| monitor_info(monitor, primary.as_ref()))
+        .collect())
+}
+
+// Monitor the user picked in settings, or the primary monitor if it is no longer connected
+pub fn preferred_monitor(window: &Window) -> tauri::Result<Option<Monitor>> {
+    if let Some(name) = settings::current(&window.app_handle()).preferred_monitor {
+        let preferred = window
+            .available_monitors()?
+            .into_iter()
+            .find(|monitor| monitor.name() == Some(&name));
+        if preferred.is_some() {
+            return Ok(preferred);
+        }
+    }
+    window.primary_monitor()
+}
+
+// Move the window onto the preferred monitor unless it is already there
+pub fn restore_preferred_monitor(window: &Window) -> tauri::Result<()> {
+    if settings::current(&window.app_handle())
+        .preferred_monitor
+        .is_none()
+    {
+        return Ok(());
+    }
+
+    let monitor = match preferred_monitor(window)? {
+        Some(monitor) => monitor,
+        None => return Ok(()),
+    };
+    let current = window.current_monitor()?;
+    if current.map_or(false, |current| is_same_monitor(&current, &monitor)) {
+        return Ok(());
+    }
+    place_on_monitor(window, &monitor, None)
+}
+
+// Emit monitors-changed whenever displays are connected, removed or rearranged
+pub fn watch_monitors(window: Window) {
+    thread::spawn(move || {
+        let mut known = list_monitors(&window).unwrap_or_default();
+        loop {
+            thread::sleep(MONITOR_POLL_INTERVAL);
+            // Changes made while paused are reported on the first poll after resuming
+            if pause::is_paused(&window.app_handle()) {
+                continue;
+            }
+            let monitors = match list_monitors(&window) {
+                Ok(monitors) => monitors,
+                Err(_) => continue,
+            };
+            if monitors != known {
+                window
+                    .emit_all("monitors-changed", &monitors)
+                    .unwrap_or_else(|err| {
+                        tracing::error!("Failed to emit monitor change: {}", err)
+                    });
+                known = monitors;
+            }
+        }
+    });
+}
+
+// List connected monitors with their work areas
+#[tauri::command]
+pub fn get_monitors(app: AppHandle) -> Result<Vec<MonitorInfo>, String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    list_monitors(&window).map_err(|err| err.to_string())
+}
+
+// Describe the monitor the main window currently sits on
+#[tauri::command]
+pub fn get_current_monitor(app: AppHandle) -> Result<Option<MonitorInfo>, String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    let primary = window.primary_monitor().map_err(|err| err.to_string())?;
+    let current = window.current_monitor().map_err(|err| err.to_string())?;
+    Ok(current.map(|monitor| monitor_info(&monitor, primary.as_ref())))
+}
+
+// Center the main window on the monitor at the given index and remember the choice
+#[tauri::command]
+pub fn move_window_to_monitor(app: AppHandle, index: usize) -> Result<(), String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    let monitor = window
+        .available_monitors()
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| format!("No monitor at index {}", index))?;
+
+    place_on_monitor(&window, &monitor, None).map_err(|err| err.to_string())?;
+    settings::update(&app, |settings| {
+        settings.preferred_monitor = monitor.name().cloned()
+    })
+}
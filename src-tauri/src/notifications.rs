@@ -0,0 +1,65 @@
+use std::thread;
+
+use notify_rust::Notification;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+// Sound played when the caller asks for an audible notification
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+const NOTIFICATION_SOUND: &str = "Default";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const NOTIFICATION_SOUND: &str = "message-new-instant";
+
+// Action reported when a notification is dismissed without being clicked
+const CLOSED_ACTION: &str = "__closed";
+
+// Notification details sent back to the frontend when the user clicks it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPayload {
+    pub title: String,
+    pub body: String,
+}
+
+// Show a native desktop notification; clicking it brings the assistant back
+#[tauri::command]
+pub fn show_notification(
+    app: AppHandle,
+    title: String,
+    body: String,
+    sound: bool,
+) -> Result<(), String> {
+    let mut notification = Notification::new();
+    notification.appname("Aura").summary(&title).body(&body);
+    if sound {
+        notification.sound_name(NOTIFICATION_SOUND);
+    }
+
+    // Waiting for the click blocks, so the notification lives on its own thread
+    let payload = NotificationPayload { title, body };
+    thread::spawn(move || {
+        let handle = match notification.show() {
+            Ok(handle) => handle,
+            Err(err) => {
+                tracing::warn!("Notification not shown, permission may be denied: {}", err);
+                return;
+            }
+        };
+
+        handle.wait_for_action(|action| {
+            if action == CLOSED_ACTION {
+                return;
+            }
+            if let Some(window) = crate::main_window(&app) {
+                if let Err(err) = crate::show_and_focus(&window) {
+                    tracing::error!("Failed to show window: {}", err);
+                }
+            }
+            app.emit_all("notification-clicked", payload)
+                .unwrap_or_else(|err| {
+                    tracing::error!("Failed to emit notification click: {}", err)
+                });
+        });
+    });
+    Ok(())
+}
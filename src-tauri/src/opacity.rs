@@ -0,0 +1,114 @@
+use std::fmt;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Window};
+
+use crate::settings;
+
+// Lowest opacity accepted, so the window never becomes invisible
+pub const MIN_OPACITY: f64 = 0.2;
+pub const MAX_OPACITY: f64 = 1.0;
+
+// Whether apply can change the opacity on this platform
+pub const SUPPORTED: bool = cfg!(any(target_os = "windows", target_os = "macos"));
+
+// Keep an opacity within the allowed range; anything unreadable means fully opaque
+pub fn clamp(opacity: f64) -> f64 {
+    if opacity.is_nan() {
+        return MAX_OPACITY;
+    }
+    opacity.clamp(MIN_OPACITY, MAX_OPACITY)
+}
+
+// Errors returned to the frontend when the opacity cannot be applied
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum OpacityError {
+    OutOfRange(String),
+    #[cfg_attr(any(target_os = "windows", target_os = "macos"), allow(dead_code))]
+    Unsupported(String),
+    Platform(String),
+}
+
+impl fmt::Display for OpacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpacityError::OutOfRange(message)
+            | OpacityError::Unsupported(message)
+            | OpacityError::Platform(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<tauri::Error> for OpacityError {
+    fn from(err: tauri::Error) -> Self {
+        OpacityError::Platform(err.to_string())
+    }
+}
+
+// Apply the opacity through the native window handle
+#[cfg(target_os = "windows")]
+pub fn apply(window: &Window, opacity: f64) -> Result<(), OpacityError> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA,
+        WS_EX_LAYERED,
+    };
+
+    let hwnd = window.hwnd()?.0;
+    unsafe {
+        let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED as isize);
+        if SetLayeredWindowAttributes(hwnd, 0, (opacity * 255.0).round() as u8, LWA_ALPHA) == 0 {
+            return Err(OpacityError::Platform(
+                "SetLayeredWindowAttributes failed".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Apply the opacity through the native window handle
+#[cfg(target_os = "macos")]
+pub fn apply(window: &Window, opacity: f64) -> Result<(), OpacityError> {
+    use cocoa::appkit::NSWindow;
+    use cocoa::base::id;
+
+    let ns_window = window.ns_window()? as id;
+    unsafe { ns_window.setAlphaValue_(opacity) };
+    Ok(())
+}
+
+// Per-window opacity depends on the compositor and is not supported here
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn apply(_window: &Window, _opacity: f64) -> Result<(), OpacityError> {
+    Err(OpacityError::Unsupported(
+        "Window opacity is not supported on this platform".to_string(),
+    ))
+}
+
+// Apply, persist and announce a new opacity for the main window
+pub fn set(app: &AppHandle, opacity: f64) -> Result<(), OpacityError> {
+    if opacity.is_nan() {
+        return Err(OpacityError::OutOfRange(format!(
+            "Opacity must be between {} and {}",
+            MIN_OPACITY, MAX_OPACITY
+        )));
+    }
+    let opacity = clamp(opacity);
+
+    let window = crate::main_window(app)
+        .ok_or_else(|| OpacityError::Platform("Main window not found".to_string()))?;
+    apply(&window, opacity)?;
+
+    settings::update(app, |settings| settings.window_opacity = opacity)
+        .map_err(OpacityError::Platform)?;
+    app.emit_all("window-opacity-changed", opacity)?;
+    Ok(())
+}
+
+// Change the main window opacity and remember it for the next launch; values outside the
+// allowed range are clamped to it
+#[tauri::command]
+pub fn set_window_opacity(app: AppHandle, opacity: f64) -> Result<(), OpacityError> {
+    set(&app, opacity)
+}
@@ -0,0 +1,97 @@
+use tauri::{AppHandle, Manager, Window, WindowBuilder, WindowEvent, WindowUrl};
+
+use crate::{monitor, shortcut_registry};
+
+// Label of the quick command window
+pub const PALETTE_WINDOW: &str = "palette";
+
+// Accelerator that summons the palette
+const PALETTE_SHORTCUT: &str = "CmdOrCtrl+Shift+Space";
+
+const PALETTE_ID: &str = "palette";
+const ESCAPE_ID: &str = "palette-escape";
+
+// Palette size in logical pixels
+const PALETTE_WIDTH: f64 = 560.0;
+const PALETTE_HEIGHT: f64 = 320.0;
+
+// Bind the palette shortcut
+pub fn register(app: &AppHandle) {
+    let app_handle = app.clone();
+    let _ = shortcut_registry::register(
+        app,
+        PALETTE_ID,
+        PALETTE_SHORTCUT,
+        "Show or hide the quick command palette",
+        move || {
+            if let Err(err) = toggle(&app_handle) {
+                tracing::error!("Failed to toggle palette: {}", err);
+            }
+        },
+    );
+}
+
+// The palette is hidden rather than closed so it opens instantly next time
+fn create(app: &AppHandle) -> tauri::Result<Window> {
+    let window = WindowBuilder::new(app, PALETTE_WINDOW, WindowUrl::App("palette.html".into()))
+        .title("Aura Quick Command")
+        .inner_size(PALETTE_WIDTH, PALETTE_HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .transparent(true)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .build()?;
+
+    let event_window = window.clone();
+    window.on_window_event(move |event| match event {
+        WindowEvent::CloseRequested { api, .. } => {
+            api.prevent_close();
+            hide(&event_window);
+        }
+        WindowEvent::Focused(focused) => {
+            let app = event_window.app_handle();
+            if *focused {
+                // Escape is only taken from other apps while the palette has focus
+                if !shortcut_registry::activate(&app, ESCAPE_ID) {
+                    let window = event_window.clone();
+                    let _ = shortcut_registry::register(
+                        &app,
+                        ESCAPE_ID,
+                        "Escape",
+                        "Dismiss the quick command palette",
+                        move || hide(&window),
+                    );
+                }
+            } else {
+                shortcut_registry::deactivate(&app, ESCAPE_ID);
+                hide(&event_window);
+            }
+        }
+        _ => {}
+    });
+    Ok(window)
+}
+
+fn hide(window: &Window) {
+    window
+        .hide()
+        .unwrap_or_else(|err| tracing::error!("Failed to hide palette: {}", err));
+}
+
+// Show the palette centered on the active monitor, or hide it when it is already up; the main
+// window is left as it is
+fn toggle(app: &AppHandle) -> tauri::Result<()> {
+    let window = match app.get_window(PALETTE_WINDOW) {
+        Some(window) => window,
+        None => create(app)?,
+    };
+    if window.is_visible()? {
+        window.hide()?;
+        return Ok(());
+    }
+    monitor::center_at_cursor(&window)?;
+    window.show()?;
+    window.set_focus()
+}
@@ -0,0 +1,70 @@
+use std::thread;
+use std::time::Duration;
+
+use arboard::Clipboard;
+use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use tauri::AppHandle;
+
+// Time for the previous app to take focus back before the keystroke is sent
+const FOCUS_SETTLE_DELAY: Duration = Duration::from_millis(150);
+
+// Modifier and key of the platform paste shortcut
+#[cfg(target_os = "macos")]
+const PASTE_MODIFIER: Key = Key::Meta;
+#[cfg(not(target_os = "macos"))]
+const PASTE_MODIFIER: Key = Key::Control;
+#[cfg(target_os = "windows")]
+const PASTE_KEY: Key = Key::V;
+#[cfg(not(target_os = "windows"))]
+const PASTE_KEY: Key = Key::Unicode('v');
+
+// macOS drops synthetic keystrokes from apps the user has not trusted for Accessibility
+#[cfg(target_os = "macos")]
+pub(crate) fn check_permission() -> Result<(), String> {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+
+    if unsafe { AXIsProcessTrusted() } {
+        Ok(())
+    } else {
+        Err(
+            "Aura needs Accessibility permission to paste into other apps. Allow it in System \
+             Settings > Privacy & Security > Accessibility, then try again."
+                .to_string(),
+        )
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn check_permission() -> Result<(), String> {
+    Ok(())
+}
+
+fn send_paste() -> Result<(), String> {
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|err| format!("Failed to start keyboard input: {}", err))?;
+    enigo
+        .key(PASTE_MODIFIER, Direction::Press)
+        .and_then(|_| enigo.key(PASTE_KEY, Direction::Click))
+        .and_then(|_| enigo.key(PASTE_MODIFIER, Direction::Release))
+        .map_err(|err| format!("Failed to send paste keystroke: {}", err))
+}
+
+// Put text on the clipboard, hand focus back to the app that was in front before Aura was
+// summoned and paste it there; async so the wait below does not stall the event loop that
+// hides the window
+#[tauri::command]
+pub async fn paste_to_active_app(app: AppHandle, text: String) -> Result<(), String> {
+    check_permission()?;
+    Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|err| format!("Failed to write clipboard: {}", err))?;
+
+    if let Some(window) = crate::main_window(&app) {
+        crate::hide_and_restore_focus(&window).map_err(|err| err.to_string())?;
+    }
+    thread::sleep(FOCUS_SETTLE_DELAY);
+    send_paste()
+}
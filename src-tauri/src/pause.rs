@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::{push_to_talk, recording, shortcut_registry, tray_state};
+
+// Tray menu item that pauses the whole assistant
+pub const TRAY_ITEM_ID: &str = "pause_aura";
+
+// While set Aura is inert: no global shortcuts, keyboard hook, microphone or background polling.
+// Commands from the window keep working; only for this session, a restart resumes
+#[derive(Default)]
+pub struct Paused(AtomicBool);
+
+// Sent whenever Aura is paused or resumed, with the pieces that could not follow
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PausedChanged {
+    pub paused: bool,
+    pub failed: Vec<String>,
+}
+
+// Whether Aura is paused
+pub fn is_paused(app: &AppHandle) -> bool {
+    app.try_state::<Paused>()
+        .map_or(false, |paused| paused.0.load(Ordering::SeqCst))
+}
+
+// Pause or resume everything at once. The flag changes even when some pieces fail, so the
+// others stay consistent; the error names the pieces that did not follow
+pub fn apply(app: &AppHandle, paused: bool) -> Result<(), String> {
+    if app.state::<Paused>().0.swap(paused, Ordering::SeqCst) == paused {
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    if paused {
+        if let Err(err) = shortcut_registry::suspend(app) {
+            failed.push(format!("global shortcuts ({})", err));
+        }
+        push_to_talk::release(app);
+        if let Err(err) = recording::stop(app) {
+            failed.push(format!("microphone ({})", err));
+        }
+    } else {
+        shortcut_registry::refresh(app);
+    }
+    if let Err(err) = tray_state::set_paused(app, paused) {
+        failed.push(format!("tray icon ({})", err));
+    }
+    tray_state::set_checked(app, TRAY_ITEM_ID, paused)
+        .unwrap_or_else(|err| tracing::error!("Failed to update tray menu: {}", err));
+
+    tracing::info!("Aura {}", if paused { "paused" } else { "resumed" });
+    app.emit_all(
+        "paused-changed",
+        PausedChanged {
+            paused,
+            failed: failed.clone(),
+        },
+    )
+    .unwrap_or_else(|err| tracing::error!("Failed to emit paused-changed: {}", err));
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        let action = if paused { "pause" } else { "resume" };
+        Err(format!("Could not {} {}", action, failed.join(", ")))
+    }
+}
+
+// Make Aura inert, or bring it back
+#[tauri::command]
+pub fn set_paused(app: AppHandle, paused: bool) -> Result<(), String> {
+    apply(&app, paused)
+}
+
+// Whether Aura is paused, for the frontend's initial state
+#[tauri::command]
+pub fn get_paused(app: AppHandle) -> bool {
+    is_paused(&app)
+}
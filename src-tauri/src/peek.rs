@@ -0,0 +1,112 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager, State, Window};
+
+use crate::monitor;
+
+#[derive(Default)]
+struct PeekState {
+    // When the window hides again; None when no peek is pending
+    deadline: Option<Instant>,
+    // Identifies the timer thread that owns the deadline
+    timer: u64,
+}
+
+// Pending auto-hide of a peeking main window
+#[derive(Default)]
+pub struct Peek(Mutex<PeekState>);
+
+// Show the window without activating it, so the app the user is typing in keeps focus
+#[cfg(target_os = "windows")]
+fn show_inactive(window: &Window) -> tauri::Result<()> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{ShowWindow, SW_SHOWNOACTIVATE};
+
+    let hwnd = window.hwnd()?.0;
+    unsafe { ShowWindow(hwnd, SW_SHOWNOACTIVATE) };
+    Ok(())
+}
+
+// Show the window without activating it, so the app the user is typing in keeps focus
+#[cfg(target_os = "macos")]
+fn show_inactive(window: &Window) -> tauri::Result<()> {
+    use cocoa::appkit::NSWindow;
+    use cocoa::base::id;
+
+    let ns_window = window.ns_window()? as id;
+    unsafe { ns_window.orderFrontRegardless() };
+    Ok(())
+}
+
+// Window managers decide focus for newly shown windows here
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn show_inactive(window: &Window) -> tauri::Result<()> {
+    window.show()
+}
+
+// Hide the window once the deadline passes, following extensions and cancellations
+fn wait_and_hide(window: Window, timer: u64) {
+    thread::spawn(move || loop {
+        let remaining = {
+            let peek = window.state::<Peek>();
+            let mut state = peek.0.lock().unwrap();
+            if state.timer != timer {
+                return;
+            }
+            match state.deadline {
+                None => return,
+                Some(at) if at <= Instant::now() => {
+                    state.deadline = None;
+                    None
+                }
+                Some(at) => Some(at - Instant::now()),
+            }
+        };
+        match remaining {
+            Some(remaining) => thread::sleep(remaining),
+            None => {
+                if let Err(err) = window.hide() {
+                    tracing::error!("Failed to hide window after peek: {}", err);
+                }
+                return;
+            }
+        }
+    });
+}
+
+// Stop a pending peek from hiding the window
+pub fn cancel(app: &AppHandle) {
+    app.state::<Peek>().0.lock().unwrap().deadline = None;
+}
+
+// Briefly show the main window without focusing it, then hide it again
+#[tauri::command]
+pub fn peek_window(app: AppHandle, peek: State<Peek>, duration_ms: u64) -> Result<(), String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    let mut state = peek.0.lock().unwrap();
+    let peeking = state.deadline.is_some();
+
+    // A window the user opened themselves is left alone
+    if !peeking && window.is_visible().map_err(|err| err.to_string())? {
+        return Ok(());
+    }
+
+    // Overlapping peeks move the deadline of the running timer instead of starting another
+    if !peeking {
+        monitor::prepare_to_show(&window);
+        show_inactive(&window).map_err(|err| err.to_string())?;
+    }
+    state.deadline = Some(Instant::now() + Duration::from_millis(duration_ms));
+    if !peeking {
+        state.timer += 1;
+        wait_and_hide(window, state.timer);
+    }
+    Ok(())
+}
+
+// Keep a peeking window open because the user started interacting with it
+#[tauri::command]
+pub fn cancel_peek(app: AppHandle) {
+    cancel(&app);
+}
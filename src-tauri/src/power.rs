@@ -0,0 +1,257 @@
+use tauri::{AppHandle, Window};
+
+use crate::push_to_talk;
+use crate::shortcut_registry;
+
+fn emit(app: &AppHandle, event: &str) {
+    if let Some(window) = crate::main_window(app) {
+        window
+            .emit(event, ())
+            .unwrap_or_else(|err| tracing::error!("Failed to emit {}: {}", event, err));
+    }
+}
+
+fn on_suspend(app: &AppHandle) {
+    push_to_talk::release(app);
+    emit(app, "system-suspend");
+}
+
+// The hook never sees the key-up of a key held while the screen locks
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn on_lock(app: &AppHandle) {
+    push_to_talk::release(app);
+}
+
+// Bind the shortcuts again after Explorer crashed and came back
+#[cfg(target_os = "windows")]
+fn on_shell_restarted(app: &AppHandle) {
+    shortcut_registry::refresh(app);
+}
+
+fn on_resume(app: &AppHandle) {
+    // Some systems drop global hotkeys across sleep, so bind them again
+    shortcut_registry::refresh(app);
+    emit(app, "system-resume");
+}
+
+// Forward sleep and wake notifications from the power manager
+#[cfg(target_os = "windows")]
+pub fn watch_power_events(app: &AppHandle) {
+    use std::ffi::c_void;
+    use windows_sys::Win32::System::Power::{
+        PowerRegisterSuspendResumeNotification, DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        DEVICE_NOTIFY_CALLBACK, PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND,
+    };
+
+    unsafe extern "system" fn callback(
+        context: *const c_void,
+        kind: u32,
+        _setting: *const c_void,
+    ) -> u32 {
+        let app = &*(context as *const AppHandle);
+        match kind {
+            PBT_APMSUSPEND => on_suspend(app),
+            PBT_APMRESUMEAUTOMATIC => on_resume(app),
+            _ => {}
+        }
+        0
+    }
+
+    // The registration lasts for the life of the process, so its parameters are leaked
+    let parameters = Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+        Callback: Some(callback),
+        Context: Box::into_raw(Box::new(app.clone())) as *mut c_void,
+    }));
+    let mut registration = std::ptr::null_mut();
+    let result = unsafe {
+        PowerRegisterSuspendResumeNotification(
+            DEVICE_NOTIFY_CALLBACK,
+            parameters as *mut DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS as isize,
+            &mut registration,
+        )
+    };
+    if result != 0 {
+        tracing::error!("Failed to watch power events: error {}", result);
+    }
+}
+
+// Forward NSWorkspace sleep and wake notifications
+#[cfg(target_os = "macos")]
+pub fn watch_power_events(app: &AppHandle) {
+    use std::ffi::c_void;
+
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::NSString;
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Class, Object, Sel};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    fn app_of(observer: &Object) -> &AppHandle {
+        unsafe { &*(*observer.get_ivar::<*const c_void>("app") as *const AppHandle) }
+    }
+    extern "C" fn will_sleep(observer: &Object, _: Sel, _: id) {
+        on_suspend(app_of(observer));
+    }
+    extern "C" fn did_wake(observer: &Object, _: Sel, _: id) {
+        on_resume(app_of(observer));
+    }
+
+    let observer_class = Class::get("AuraPowerObserver").unwrap_or_else(|| {
+        let mut decl = ClassDecl::new("AuraPowerObserver", class!(NSObject))
+            .expect("AuraPowerObserver is declared once");
+        decl.add_ivar::<*const c_void>("app");
+        unsafe {
+            decl.add_method(
+                sel!(willSleep:),
+                will_sleep as extern "C" fn(&Object, Sel, id),
+            );
+            decl.add_method(sel!(didWake:), did_wake as extern "C" fn(&Object, Sel, id));
+        }
+        decl.register()
+    });
+
+    // The observer lives for the life of the process, so it and its handle are leaked
+    unsafe {
+        let observer: id = msg_send![observer_class, new];
+        (*observer).set_ivar("app", Box::into_raw(Box::new(app.clone())) as *const c_void);
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let center: id = msg_send![workspace, notificationCenter];
+        let notifications = [
+            (sel!(willSleep:), "NSWorkspaceWillSleepNotification"),
+            (sel!(didWake:), "NSWorkspaceDidWakeNotification"),
+        ];
+        for (selector, name) in notifications {
+            let name = NSString::alloc(nil).init_str(name);
+            let _: () =
+                msg_send![center, addObserver: observer selector: selector name: name object: nil];
+        }
+    }
+}
+
+// Without a portable sleep notification, infer a resume from a jump in wall-clock time
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn watch_power_events(app: &AppHandle) {
+    use std::thread;
+    use std::time::{Duration, SystemTime};
+
+    const POLL_INTERVAL: Duration = Duration::from_secs(5);
+    const SLEEP_GAP: Duration = Duration::from_secs(30);
+
+    let app = app.clone();
+    thread::spawn(move || {
+        let mut last = SystemTime::now();
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let now = SystemTime::now();
+            let elapsed = now.duration_since(last).unwrap_or_default();
+            if elapsed > POLL_INTERVAL + SLEEP_GAP {
+                on_resume(&app);
+            }
+            last = now;
+        }
+    });
+}
+
+// Listen for session lock and Explorer restart messages sent to the main window
+#[cfg(target_os = "windows")]
+pub fn watch_session_events(window: &Window) -> tauri::Result<()> {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tauri::Manager;
+    use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows_sys::Win32::System::RemoteDesktop::{
+        WTSRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION,
+    };
+    use windows_sys::Win32::UI::Shell::{DefSubclassProc, SetWindowSubclass};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        RegisterWindowMessageW, WM_WTSSESSION_CHANGE, WTS_SESSION_LOCK,
+    };
+
+    // Identifies this subclass among others installed on the same window
+    const SUBCLASS_ID: usize = 1;
+
+    // Message id Explorer broadcasts to top-level windows after it restarts
+    static TASKBAR_CREATED: AtomicU32 = AtomicU32::new(0);
+
+    unsafe extern "system" fn subclass(
+        hwnd: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _id: usize,
+        app: usize,
+    ) -> LRESULT {
+        if message == WM_WTSSESSION_CHANGE && wparam as u32 == WTS_SESSION_LOCK {
+            on_lock(&*(app as *const AppHandle));
+        } else if message != 0 && message == TASKBAR_CREATED.load(Ordering::Relaxed) {
+            on_shell_restarted(&*(app as *const AppHandle));
+        }
+        DefSubclassProc(hwnd, message, wparam, lparam)
+    }
+
+    // The subclass lasts as long as the window, so its handle is leaked
+    let hwnd = window.hwnd()?.0;
+    let app = Box::into_raw(Box::new(window.app_handle())) as usize;
+    let name: Vec<u16> = "TaskbarCreated\0".encode_utf16().collect();
+    unsafe {
+        TASKBAR_CREATED.store(RegisterWindowMessageW(name.as_ptr()), Ordering::Relaxed);
+        if SetWindowSubclass(hwnd, Some(subclass), SUBCLASS_ID, app) == 0
+            || WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION) == 0
+        {
+            tracing::error!("Failed to watch for session events");
+        }
+    }
+    Ok(())
+}
+
+// Listen for the distributed notification posted when the screen locks
+#[cfg(target_os = "macos")]
+pub fn watch_session_events(window: &Window) -> tauri::Result<()> {
+    use std::ffi::c_void;
+
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::NSString;
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Class, Object, Sel};
+    use objc::{class, msg_send, sel, sel_impl};
+    use tauri::Manager;
+
+    extern "C" fn screen_locked(observer: &Object, _: Sel, _: id) {
+        let app = unsafe { &*(*observer.get_ivar::<*const c_void>("app") as *const AppHandle) };
+        on_lock(app);
+    }
+
+    let observer_class = Class::get("AuraLockObserver").unwrap_or_else(|| {
+        let mut decl = ClassDecl::new("AuraLockObserver", class!(NSObject))
+            .expect("AuraLockObserver is declared once");
+        decl.add_ivar::<*const c_void>("app");
+        unsafe {
+            decl.add_method(
+                sel!(screenLocked:),
+                screen_locked as extern "C" fn(&Object, Sel, id),
+            );
+        }
+        decl.register()
+    });
+
+    // The observer lives for the life of the process, so it and its handle are leaked
+    unsafe {
+        let observer: id = msg_send![observer_class, new];
+        (*observer).set_ivar(
+            "app",
+            Box::into_raw(Box::new(window.app_handle())) as *const c_void,
+        );
+        let center: id = msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+        let name = NSString::alloc(nil).init_str("com.apple.screenIsLocked");
+        let _: () = msg_send![center, addObserver: observer selector: sel!(screenLocked:) name: name object: nil];
+    }
+    Ok(())
+}
+
+// Screen lock is not reported on other platforms; focus loss still ends push-to-talk
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn watch_session_events(_window: &Window) -> tauri::Result<()> {
+    Ok(())
+}
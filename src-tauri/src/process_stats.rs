@@ -0,0 +1,50 @@
+use std::sync::Mutex;
+use std::thread;
+
+use serde::Serialize;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use tauri::State;
+
+// Resource usage of the Aura process
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessStats {
+    pub memory_bytes: u64,
+    pub cpu_percent: f32,
+}
+
+// Cached sysinfo handle; CPU usage is measured between consecutive refreshes
+pub struct ProcessMonitor {
+    system: Mutex<System>,
+    pid: Pid,
+}
+
+impl Default for ProcessMonitor {
+    fn default() -> Self {
+        ProcessMonitor {
+            system: Mutex::new(System::new()),
+            pid: Pid::from_u32(std::process::id()),
+        }
+    }
+}
+
+// Report Aura's resident memory and CPU share; the first call reports 0% CPU because
+// there is no earlier sample to compare against
+#[tauri::command]
+pub fn get_process_stats(monitor: State<ProcessMonitor>) -> Result<ProcessStats, String> {
+    let mut system = monitor.system.lock().unwrap();
+    system.refresh_process_specifics(
+        monitor.pid,
+        ProcessRefreshKind::new().with_cpu().with_memory(),
+    );
+    let process = system
+        .process(monitor.pid)
+        .ok_or("Aura process not found")?;
+
+    // sysinfo reports 100% per core; scale to a share of the whole machine
+    let cores = thread::available_parallelism().map_or(1, |cores| cores.get());
+    Ok(ProcessStats {
+        memory_bytes: process.memory(),
+        cpu_percent: process.cpu_usage() / cores as f32,
+    })
+}
@@ -0,0 +1,316 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rdev::{EventType, Key};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::{double_tap, pause, settings};
+
+// Accelerator held to talk when none is configured. The hook passes keys through, so it avoids
+// Spotlight and the input-language switch on CmdOrCtrl+Space
+pub const DEFAULT_PUSH_TO_TALK_SHORTCUT: &str = "Ctrl+Alt+Shift+Space";
+
+// Modifier keys, merging the left and right variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Modifier {
+    Control,
+    Meta,
+    Alt,
+    Shift,
+}
+
+impl Modifier {
+    pub(crate) fn of(key: Key) -> Option<Self> {
+        match key {
+            Key::ControlLeft | Key::ControlRight => Some(Modifier::Control),
+            Key::MetaLeft | Key::MetaRight => Some(Modifier::Meta),
+            Key::Alt | Key::AltGr => Some(Modifier::Alt),
+            Key::ShiftLeft | Key::ShiftRight => Some(Modifier::Shift),
+            _ => None,
+        }
+    }
+}
+
+// Parsed push-to-talk accelerator
+#[derive(Debug, Clone, PartialEq)]
+struct Hotkey {
+    modifiers: HashSet<Modifier>,
+    key: Key,
+}
+
+impl Hotkey {
+    // Parse an accelerator in the same format as the global shortcuts, e.g. "CmdOrCtrl+Space"
+    fn parse(accelerator: &str) -> Result<Self, String> {
+        let mut modifiers = HashSet::new();
+        let mut key = None;
+        for part in accelerator.split('+').map(str::trim) {
+            let modifier = match part.to_uppercase().as_str() {
+                "CMDORCTRL" | "COMMANDORCONTROL" if cfg!(target_os = "macos") => Modifier::Meta,
+                "CMDORCTRL" | "COMMANDORCONTROL" | "CTRL" | "CONTROL" => Modifier::Control,
+                "CMD" | "COMMAND" | "SUPER" | "META" => Modifier::Meta,
+                "ALT" | "OPTION" => Modifier::Alt,
+                "SHIFT" => Modifier::Shift,
+                _ => {
+                    if key.is_some() {
+                        return Err(format!(
+                            "Shortcut '{}' has more than one non-modifier key",
+                            accelerator
+                        ));
+                    }
+                    key = Some(parse_key(part).ok_or_else(|| {
+                        format!("Unknown key '{}' in shortcut '{}'", part, accelerator)
+                    })?);
+                    continue;
+                }
+            };
+            modifiers.insert(modifier);
+        }
+
+        let key = key.ok_or_else(|| format!("Shortcut '{}' has no key", accelerator))?;
+        Ok(Hotkey { modifiers, key })
+    }
+}
+
+fn parse_key(name: &str) -> Option<Key> {
+    let key = match name.to_uppercase().as_str() {
+        "A" => Key::KeyA,
+        "B" => Key::KeyB,
+        "C" => Key::KeyC,
+        "D" => Key::KeyD,
+        "E" => Key::KeyE,
+        "F" => Key::KeyF,
+        "G" => Key::KeyG,
+        "H" => Key::KeyH,
+        "I" => Key::KeyI,
+        "J" => Key::KeyJ,
+        "K" => Key::KeyK,
+        "L" => Key::KeyL,
+        "M" => Key::KeyM,
+        "N" => Key::KeyN,
+        "O" => Key::KeyO,
+        "P" => Key::KeyP,
+        "Q" => Key::KeyQ,
+        "R" => Key::KeyR,
+        "S" => Key::KeyS,
+        "T" => Key::KeyT,
+        "U" => Key::KeyU,
+        "V" => Key::KeyV,
+        "W" => Key::KeyW,
+        "X" => Key::KeyX,
+        "Y" => Key::KeyY,
+        "Z" => Key::KeyZ,
+        "0" => Key::Num0,
+        "1" => Key::Num1,
+        "2" => Key::Num2,
+        "3" => Key::Num3,
+        "4" => Key::Num4,
+        "5" => Key::Num5,
+        "6" => Key::Num6,
+        "7" => Key::Num7,
+        "8" => Key::Num8,
+        "9" => Key::Num9,
+        "F1" => Key::F1,
+        "F2" => Key::F2,
+        "F3" => Key::F3,
+        "F4" => Key::F4,
+        "F5" => Key::F5,
+        "F6" => Key::F6,
+        "F7" => Key::F7,
+        "F8" => Key::F8,
+        "F9" => Key::F9,
+        "F10" => Key::F10,
+        "F11" => Key::F11,
+        "F12" => Key::F12,
+        "SPACE" => Key::Space,
+        "ENTER" | "RETURN" => Key::Return,
+        "TAB" => Key::Tab,
+        "ESC" | "ESCAPE" => Key::Escape,
+        "BACKSPACE" => Key::Backspace,
+        "UP" => Key::UpArrow,
+        "DOWN" => Key::DownArrow,
+        "LEFT" => Key::LeftArrow,
+        "RIGHT" => Key::RightArrow,
+        "'" | "QUOTE" => Key::Quote,
+        "`" | "BACKQUOTE" => Key::BackQuote,
+        ";" => Key::SemiColon,
+        "," => Key::Comma,
+        "." => Key::Dot,
+        "/" => Key::Slash,
+        _ => return None,
+    };
+    Some(key)
+}
+
+// When a push-to-talk key went down or up, in epoch milliseconds
+#[derive(Debug, Clone, Serialize)]
+pub struct PushToTalkPayload {
+    pub timestamp: u64,
+}
+
+// Push-to-talk hook shared between the listener thread and commands
+pub struct PushToTalk {
+    hotkey: Arc<Mutex<Hotkey>>,
+    talking: Arc<AtomicBool>,
+    enabled: Arc<AtomicBool>,
+    running: Arc<AtomicBool>,
+    // Set once the keyboard hook is installed; rdev cannot remove it again
+    hooked: AtomicBool,
+}
+
+// Track the configured key in a low-level keyboard hook and emit ptt-pressed/ptt-released
+fn listen(app: AppHandle, push_to_talk: &PushToTalk) {
+    let hotkey = push_to_talk.hotkey.clone();
+    let talking = push_to_talk.talking.clone();
+    let enabled = push_to_talk.enabled.clone();
+    let running = push_to_talk.running.clone();
+
+    // rdev::listen blocks for the life of the process, so the hook gets its own thread
+    thread::spawn(move || {
+        let mut held = HashSet::new();
+        let result = rdev::listen(move |event| {
+            if !running.load(Ordering::SeqCst) || pause::is_paused(&app) {
+                return;
+            }
+            double_tap::on_event(&app, &event);
+            if !enabled.load(Ordering::SeqCst) {
+                return;
+            }
+            let hotkey = hotkey.lock().unwrap();
+            match event.event_type {
+                EventType::KeyPress(key) => {
+                    if let Some(modifier) = Modifier::of(key) {
+                        held.insert(modifier);
+                    }
+                    // Key repeat sends more presses while held; only the first one starts talking
+                    if key == hotkey.key
+                        && hotkey.modifiers.is_subset(&held)
+                        && !talking.swap(true, Ordering::SeqCst)
+                    {
+                        emit(&app, "ptt-pressed", event.time);
+                    }
+                }
+                EventType::KeyRelease(key) => {
+                    let modifier = Modifier::of(key);
+                    if let Some(modifier) = modifier {
+                        held.remove(&modifier);
+                    }
+                    // Letting go of the key or any of its modifiers ends the utterance
+                    let part_of_hotkey = key == hotkey.key
+                        || modifier.map_or(false, |modifier| hotkey.modifiers.contains(&modifier));
+                    if part_of_hotkey && talking.swap(false, Ordering::SeqCst) {
+                        emit(&app, "ptt-released", event.time);
+                    }
+                }
+                _ => {}
+            }
+        });
+        if let Err(err) = result {
+            tracing::warn!(
+                "Push-to-talk unavailable, input monitoring may be denied: {:?}",
+                err
+            );
+        }
+    });
+}
+
+fn emit(app: &AppHandle, event: &str, time: SystemTime) {
+    let timestamp = time
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+    if let Some(window) = crate::main_window(app) {
+        window
+            .emit(event, PushToTalkPayload { timestamp })
+            .unwrap_or_else(|err| tracing::error!("Failed to emit {}: {}", event, err));
+    }
+}
+
+// End an utterance whose key-up the hook will never see, e.g. after focus loss or a screen lock
+pub fn release(app: &AppHandle) {
+    if let Some(push_to_talk) = app.try_state::<PushToTalk>() {
+        if push_to_talk.talking.swap(false, Ordering::SeqCst) {
+            emit(app, "ptt-released", SystemTime::now());
+        }
+    }
+}
+
+// Install the keyboard hook the first time push-to-talk or double-tap activation needs it.
+// Hooking every keystroke is only worth it for those, and on macOS it asks for Input
+// Monitoring, so nothing is hooked while both are off
+pub fn ensure_hook(app: &AppHandle) {
+    let push_to_talk = match app.try_state::<PushToTalk>() {
+        Some(push_to_talk) => push_to_talk,
+        None => return,
+    };
+    let needed = push_to_talk.enabled.load(Ordering::SeqCst)
+        || settings::current(app).double_tap_activation.enabled;
+    if needed && !push_to_talk.hooked.swap(true, Ordering::SeqCst) {
+        listen(app.clone(), &push_to_talk);
+    }
+}
+
+// Set up push-to-talk with the configured accelerator, hooking the keyboard only if it or
+// double-tap activation is enabled
+pub fn start(app: &AppHandle, accelerator: &str, enabled: bool) {
+    let hotkey = Hotkey::parse(accelerator).unwrap_or_else(|err| {
+        tracing::warn!("{}, using {}", err, DEFAULT_PUSH_TO_TALK_SHORTCUT);
+        Hotkey::parse(DEFAULT_PUSH_TO_TALK_SHORTCUT).expect("default shortcut is valid")
+    });
+
+    let push_to_talk = PushToTalk {
+        hotkey: Arc::new(Mutex::new(hotkey)),
+        talking: Arc::new(AtomicBool::new(false)),
+        enabled: Arc::new(AtomicBool::new(enabled)),
+        running: Arc::new(AtomicBool::new(true)),
+        hooked: AtomicBool::new(false),
+    };
+    app.manage(push_to_talk);
+    ensure_hook(app);
+}
+
+// Stop reacting to the hook and close any utterance still in progress
+pub fn shutdown(app: &AppHandle) {
+    if let Some(push_to_talk) = app.try_state::<PushToTalk>() {
+        push_to_talk.running.store(false, Ordering::SeqCst);
+    }
+    release(app);
+}
+
+// Switch the push-to-talk accelerator and persist it
+pub fn apply(app: &AppHandle, accelerator: String) -> Result<(), String> {
+    let hotkey = Hotkey::parse(&accelerator)?;
+    let push_to_talk = app.state::<PushToTalk>();
+    *push_to_talk.hotkey.lock().unwrap() = hotkey;
+    // The old key's release would no longer match, so end any utterance now
+    release(app);
+    settings::update(app, |settings| settings.push_to_talk_shortcut = accelerator)
+}
+
+// Turn the push-to-talk hook on or off and persist the choice
+pub fn apply_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    app.state::<PushToTalk>()
+        .enabled
+        .store(enabled, Ordering::SeqCst);
+    if !enabled {
+        release(app);
+    }
+    settings::update(app, |settings| settings.push_to_talk_enabled = enabled)?;
+    ensure_hook(app);
+    Ok(())
+}
+
+// Change the key held for push-to-talk
+#[tauri::command]
+pub fn set_ptt_key(app: AppHandle, accelerator: String) -> Result<(), String> {
+    apply(&app, accelerator)
+}
+
+// Enable or disable push-to-talk
+#[tauri::command]
+pub fn set_ptt_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply_enabled(&app, enabled)
+}
@@ -0,0 +1,392 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use cpal::traits::{DeviceTrait, StreamTrait};
+use cpal::{Device, FromSample, SampleFormat, SizedSample, Stream, StreamConfig};
+use hound::{WavSpec, WavWriter};
+use ringbuf::{HeapConsumer, HeapProducer, HeapRb};
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::{audio_devices, pause};
+
+// Format of the recorded file, as expected by the speech-to-text providers
+const OUTPUT_SAMPLE_RATE: u32 = 16_000;
+
+// Seconds of audio the ring buffer holds while the writer catches up
+const BUFFER_SECONDS: usize = 2;
+
+// How often the writer drains the ring buffer
+const DRAIN_INTERVAL: Duration = Duration::from_millis(20);
+
+// Output samples per audio-level event, 100ms at 16 kHz
+const LEVEL_WINDOW: usize = OUTPUT_SAMPLE_RATE as usize / 10;
+
+// Corner of the low-pass filter run before resampling, under the 8 kHz limit of the output rate
+// so higher frequencies do not fold back into the speech band
+const ANTI_ALIAS_CUTOFF: f64 = 7_000.0;
+
+// Q of the two sections of a fourth-order Butterworth filter
+const BUTTERWORTH_Q: [f64; 2] = [0.541_196_1, 1.306_563];
+
+// Loudness of the last 100ms of audio, for the live meter
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioLevel {
+    pub rms: f32,
+}
+
+struct Recording {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<Result<PathBuf, String>>,
+}
+
+// Microphone recording in progress, if any
+#[derive(Default)]
+pub struct Recorder(Mutex<Option<Recording>>);
+
+// Second-order low-pass section, from the Audio EQ Cookbook
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    x1: f64,
+    x2: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Biquad {
+    fn low_pass(sample_rate: f64, cutoff: f64, q: f64) -> Self {
+        let w0 = 2.0 * std::f64::consts::PI * cutoff / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let a0 = 1.0 + alpha;
+        let b1 = (1.0 - w0.cos()) / a0;
+        Biquad {
+            b0: b1 / 2.0,
+            b1,
+            b2: b1 / 2.0,
+            a1: -2.0 * w0.cos() / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+// Anti-aliasing filter for a device rate; nothing is filtered when there is no downsampling
+fn anti_alias(sample_rate: f64) -> Vec<Biquad> {
+    if sample_rate <= OUTPUT_SAMPLE_RATE as f64 {
+        return Vec::new();
+    }
+    BUTTERWORTH_Q
+        .iter()
+        .map(|&q| Biquad::low_pass(sample_rate, ANTI_ALIAS_CUTOFF, q))
+        .collect()
+}
+
+// Downmixes the device frames, resamples them to 16 kHz and writes the WAV file
+struct Encoder {
+    app: AppHandle,
+    writer: WavWriter<BufWriter<File>>,
+    channels: usize,
+    filter: Vec<Biquad>,
+    // Linear interpolation between the previous and the current input frame
+    step: f64,
+    position: f64,
+    previous: f32,
+    level_sum: f32,
+    level_count: usize,
+}
+
+impl Encoder {
+    fn push_frame(&mut self, sample: f32) -> Result<(), hound::Error> {
+        let sample = self
+            .filter
+            .iter_mut()
+            .fold(sample as f64, |value, stage| stage.process(value)) as f32;
+        while self.position <= 1.0 {
+            let value = self.previous + (sample - self.previous) * self.position as f32;
+            self.writer
+                .write_sample((value.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+            self.record_level(value);
+            self.position += self.step;
+        }
+        self.position -= 1.0;
+        self.previous = sample;
+        Ok(())
+    }
+
+    fn record_level(&mut self, value: f32) {
+        self.level_sum += value * value;
+        self.level_count += 1;
+        if self.level_count < LEVEL_WINDOW {
+            return;
+        }
+        let rms = (self.level_sum / self.level_count as f32).sqrt();
+        self.level_sum = 0.0;
+        self.level_count = 0;
+        if let Some(window) = crate::main_window(&self.app) {
+            window
+                .emit("audio-level", AudioLevel { rms })
+                .unwrap_or_else(|err| tracing::error!("Failed to emit audio level: {}", err));
+        }
+    }
+
+    // Write every complete frame waiting in the ring buffer
+    fn drain(&mut self, consumer: &mut HeapConsumer<f32>) -> Result<(), hound::Error> {
+        let mut frame = vec![0.0; self.channels];
+        while consumer.len() >= self.channels {
+            consumer.pop_slice(&mut frame);
+            let mono = frame.iter().sum::<f32>() / self.channels as f32;
+            self.push_frame(mono)?;
+        }
+        Ok(())
+    }
+}
+
+// Input stream converting every sample to f32 and handing it to the writer thread
+fn build_stream<T>(
+    device: &Device,
+    config: &StreamConfig,
+    mut producer: HeapProducer<f32>,
+    dropped: Arc<AtomicUsize>,
+) -> Result<Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            for &sample in data {
+                if producer.push(sample.to_sample::<f32>()).is_err() {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        },
+        |err| tracing::error!("Microphone stream error: {}", err),
+        None,
+    )
+}
+
+// Open the microphone and start filling the ring buffer
+fn open_input(
+    device: Device,
+    dropped: Arc<AtomicUsize>,
+) -> Result<(Stream, HeapConsumer<f32>, StreamConfig), String> {
+    let supported = device
+        .default_input_config()
+        .map_err(|err| format!("Failed to read microphone format: {}", err))?;
+    let config = supported.config();
+
+    let capacity = config.sample_rate.0 as usize * config.channels as usize * BUFFER_SECONDS;
+    let (producer, consumer) = HeapRb::<f32>::new(capacity).split();
+    let stream = match supported.sample_format() {
+        SampleFormat::F32 => build_stream::<f32>(&device, &config, producer, dropped),
+        SampleFormat::I16 => build_stream::<i16>(&device, &config, producer, dropped),
+        SampleFormat::U16 => build_stream::<u16>(&device, &config, producer, dropped),
+        format => return Err(format!("Unsupported microphone format {:?}", format)),
+    }
+    .map_err(|err| format!("Failed to open microphone: {}", err))?;
+    stream
+        .play()
+        .map_err(|err| format!("Failed to start microphone: {}", err))?;
+    Ok((stream, consumer, config))
+}
+
+// Body of the recording thread; it owns the stream because cpal streams cannot change threads
+fn record(
+    app: AppHandle,
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+    started: mpsc::Sender<Result<(), String>>,
+) -> Result<PathBuf, String> {
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let setup = audio_devices::input_device(&app)
+        .and_then(|device| open_input(device, dropped.clone()))
+        .and_then(|(stream, consumer, config)| {
+            let spec = WavSpec {
+                channels: 1,
+                sample_rate: OUTPUT_SAMPLE_RATE,
+                bits_per_sample: 16,
+                sample_format: hound::SampleFormat::Int,
+            };
+            let writer = WavWriter::create(&path, spec)
+                .map_err(|err| format!("Failed to create '{}': {}", path.display(), err))?;
+            Ok((stream, consumer, config, writer))
+        });
+    let (stream, mut consumer, config, writer) = match setup {
+        Ok(parts) => {
+            let _ = started.send(Ok(()));
+            parts
+        }
+        Err(err) => {
+            let _ = started.send(Err(err.clone()));
+            return Err(err);
+        }
+    };
+
+    let mut encoder = Encoder {
+        app,
+        writer,
+        channels: config.channels as usize,
+        filter: anti_alias(config.sample_rate.0 as f64),
+        step: config.sample_rate.0 as f64 / OUTPUT_SAMPLE_RATE as f64,
+        position: 1.0,
+        previous: 0.0,
+        level_sum: 0.0,
+        level_count: 0,
+    };
+    let write_error = |err: hound::Error| format!("Failed to write '{}': {}", path.display(), err);
+
+    while !stop.load(Ordering::SeqCst) {
+        thread::sleep(DRAIN_INTERVAL);
+        encoder.drain(&mut consumer).map_err(write_error)?;
+    }
+
+    // Samples captured before the stream closes still belong to the recording
+    drop(stream);
+    encoder.drain(&mut consumer).map_err(write_error)?;
+    encoder.writer.finalize().map_err(write_error)?;
+
+    let dropped = dropped.load(Ordering::Relaxed);
+    if dropped > 0 {
+        tracing::warn!(
+            "Recording dropped {} samples, the writer fell behind",
+            dropped
+        );
+    }
+    Ok(path)
+}
+
+// Where a new recording is written, under the app cache directory
+fn recording_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path_resolver()
+        .app_cache_dir()
+        .ok_or("Cache directory not available")?
+        .join("recordings");
+    fs::create_dir_all(&dir)
+        .map_err(|err| format!("Failed to create '{}': {}", dir.display(), err))?;
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    Ok(dir.join(format!("recording-{}.wav", millis)))
+}
+
+fn start(app: &AppHandle) -> Result<(), String> {
+    if pause::is_paused(app) {
+        return Err("Aura is paused".into());
+    }
+    let recorder = app.state::<Recorder>();
+    let mut recording = recorder.0.lock().unwrap();
+    if recording.is_some() {
+        return Err("Already recording".into());
+    }
+
+    let path = recording_path(app)?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let (started, on_started) = mpsc::channel();
+    let thread = {
+        let app = app.clone();
+        let stop = stop.clone();
+        thread::spawn(move || record(app, path, stop, started))
+    };
+
+    // Report a missing or busy microphone here rather than from stop_recording
+    match on_started.recv() {
+        Ok(Ok(())) => {
+            *recording = Some(Recording { stop, thread });
+            Ok(())
+        }
+        Ok(Err(err)) => Err(err),
+        Err(_) => Err("Recording thread stopped unexpectedly".into()),
+    }
+}
+
+// Start recording the chosen microphone; audio-level events follow until it is stopped. Opening
+// the device can take a while, so it is waited for off the main thread
+#[tauri::command]
+pub async fn start_recording(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || start(&app))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+fn finish(recording: Recording) -> Result<PathBuf, String> {
+    recording.stop.store(true, Ordering::SeqCst);
+    recording
+        .thread
+        .join()
+        .map_err(|_| "Recording thread panicked".to_string())?
+}
+
+// Close the microphone if a recording is in progress, keeping what was recorded so far
+pub fn stop(app: &AppHandle) -> Result<(), String> {
+    let recording = app.state::<Recorder>().0.lock().unwrap().take();
+    match recording {
+        Some(recording) => finish(recording).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+// Stop the current recording and return the path of the 16 kHz mono WAV file
+#[tauri::command]
+pub fn stop_recording(recorder: State<'_, Recorder>) -> Result<String, String> {
+    let recording = recorder.0.lock().unwrap().take().ok_or("Not recording")?;
+    let path = finish(recording)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Peak level of a sine after the filter has settled
+    fn filtered_peak(sample_rate: f64, frequency: f64) -> f64 {
+        let mut filter = anti_alias(sample_rate);
+        (0..sample_rate as usize)
+            .map(|n| {
+                let x = (2.0 * std::f64::consts::PI * frequency * n as f64 / sample_rate).sin();
+                filter
+                    .iter_mut()
+                    .fold(x, |value, stage| stage.process(value))
+            })
+            .skip(sample_rate as usize / 2)
+            .fold(0.0, |peak: f64, value| peak.max(value.abs()))
+    }
+
+    #[test]
+    fn passes_speech_and_cuts_above_the_output_nyquist() {
+        assert!(filtered_peak(48_000.0, 1_000.0) > 0.95);
+        assert!(filtered_peak(48_000.0, 12_000.0) < 0.1);
+        assert!(filtered_peak(44_100.0, 15_000.0) < 0.05);
+    }
+
+    #[test]
+    fn leaves_audio_at_the_output_rate_alone() {
+        assert!(anti_alias(16_000.0).is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::files;
+
+// Where the file manager was opened; exact is false when the path no longer exists and its
+// nearest existing folder was shown instead
+#[derive(Debug, Clone, Serialize)]
+pub struct Revealed {
+    pub path: String,
+    pub exact: bool,
+}
+
+// windows-sys 0.52 leaves out the item id list functions, so they are declared here with the
+// list as an opaque pointer
+#[cfg(target_os = "windows")]
+#[link(name = "shell32")]
+extern "system" {
+    fn ILCreateFromPathW(path: *const u16) -> *mut std::ffi::c_void;
+    fn ILFree(item: *mut std::ffi::c_void);
+    fn SHOpenFolderAndSelectItems(
+        folder: *const std::ffi::c_void,
+        count: u32,
+        items: *const *const std::ffi::c_void,
+        flags: u32,
+    ) -> windows_sys::core::HRESULT;
+}
+
+// NUL-terminated UTF-16 path for the shell. It is passed as is, with no command line to quote
+// spaces or commas for
+#[cfg(target_os = "windows")]
+fn wide_path(path: &Path) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+
+    path.as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+// Open Explorer with the item selected. SHOpenFolderAndSelectItems is what `explorer /select,`
+// does, without having to quote paths with spaces or commas on its command line
+#[cfg(target_os = "windows")]
+fn select_item(path: &Path) -> Result<(), String> {
+    use std::ptr;
+
+    use windows_sys::Win32::System::Com::{
+        CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED,
+    };
+
+    let wide = wide_path(path);
+    // The shell needs COM on the calling thread, so the call gets a thread of its own
+    std::thread::spawn(move || unsafe {
+        let initialized = CoInitializeEx(ptr::null(), COINIT_APARTMENTTHREADED as u32) >= 0;
+        let item = ILCreateFromPathW(wide.as_ptr());
+        let result = if item.is_null() {
+            Err("The shell could not resolve the path".to_string())
+        } else {
+            let hresult = SHOpenFolderAndSelectItems(item, 0, ptr::null(), 0);
+            ILFree(item);
+            if hresult >= 0 {
+                Ok(())
+            } else {
+                Err(format!("SHOpenFolderAndSelectItems failed: {:#x}", hresult))
+            }
+        };
+        if initialized {
+            CoUninitialize();
+        }
+        result
+    })
+    .join()
+    .map_err(|_| "Explorer call panicked".to_string())?
+}
+
+#[cfg(target_os = "macos")]
+fn select_item(path: &Path) -> Result<(), String> {
+    let status = std::process::Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .status()
+        .map_err(|err| format!("Failed to run open: {}", err))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("open -R exited with {}", status))
+    }
+}
+
+// File URI for ShowItems; dbus-send splits array items at commas, so those are escaped too
+#[cfg(target_os = "linux")]
+fn show_items_uri(path: &Path) -> Option<String> {
+    tauri::Url::from_file_path(path)
+        .ok()
+        .map(|url| url.as_str().replace(',', "%2C"))
+}
+
+// Ask the file manager to select the item over DBus, which Nautilus, Dolphin, Nemo and others
+// implement; otherwise open the folder holding it
+#[cfg(target_os = "linux")]
+fn select_item(path: &Path) -> Result<(), String> {
+    let uri = show_items_uri(path)
+        .ok_or_else(|| format!("'{}' is not an absolute path", path.display()))?;
+    let shown = std::process::Command::new("dbus-send")
+        .args([
+            "--session",
+            "--print-reply",
+            "--dest=org.freedesktop.FileManager1",
+            "/org/freedesktop/FileManager1",
+            "org.freedesktop.FileManager1.ShowItems",
+        ])
+        .arg(format!("array:string:{}", uri))
+        .arg("string:")
+        .status()
+        .map_or(false, |status| status.success());
+    if shown {
+        return Ok(());
+    }
+    let parent = path.parent().unwrap_or(path);
+    open::that(parent).map_err(|err| err.to_string())
+}
+
+// Deepest part of the path that still exists, and whether that is the path itself
+fn nearest_existing(path: &Path) -> Option<(PathBuf, bool)> {
+    path.ancestors()
+        .enumerate()
+        .find(|(_, ancestor)| ancestor.exists())
+        .map(|(depth, ancestor)| (ancestor.to_path_buf(), depth == 0))
+}
+
+// Show a file or folder selected in Explorer, Finder or the Linux file manager. A path that
+// has gone missing opens its nearest existing folder, reported with exact set to false. Only
+// places inside the home directory or the file command roots are shown
+#[tauri::command]
+pub async fn reveal_in_file_manager(app: AppHandle, path: String) -> Result<Revealed, String> {
+    let requested = Path::new(&path);
+    if !requested.is_absolute() {
+        return Err(format!("'{}' is not an absolute path", path));
+    }
+    let (target, exact) =
+        nearest_existing(requested).ok_or_else(|| format!("No part of '{}' exists", path))?;
+    let resolved = target
+        .canonicalize()
+        .map_err(|err| format!("'{}': {}", target.display(), err))?;
+    if !files::home_roots(&app)
+        .iter()
+        .any(|root| resolved.starts_with(root))
+    {
+        return Err(format!("'{}' is outside the home directory", path));
+    }
+
+    if exact {
+        select_item(&target)
+    } else {
+        open::that(&target).map_err(|err| err.to_string())
+    }
+    .map_err(|err| format!("Failed to reveal '{}': {}", target.display(), err))?;
+
+    Ok(Revealed {
+        path: target.to_string_lossy().to_string(),
+        exact,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn nearest_existing_finds_the_closest_ancestor() {
+        let dir = std::env::temp_dir().join(format!("aura-reveal-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("notes.txt");
+        fs::write(&file, "").unwrap();
+
+        let exact = nearest_existing(&file);
+        let missing_leaf = nearest_existing(&dir.join("gone.txt"));
+        let missing_parents = nearest_existing(&dir.join("a").join("b").join("gone.txt"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(exact, Some((file, true)));
+        assert_eq!(missing_leaf, Some((dir.clone(), false)));
+        assert_eq!(missing_parents, Some((dir, false)));
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn shell_takes_paths_with_spaces_and_commas_unquoted() {
+        use std::ffi::OsString;
+        use std::os::windows::ffi::OsStringExt;
+
+        let path = Path::new(r"C:\a b\c,d.txt");
+        let wide = wide_path(path);
+        assert_eq!(wide.last(), Some(&0));
+        assert_eq!(
+            OsString::from_wide(&wide[..wide.len() - 1]),
+            path.as_os_str()
+        );
+
+        // The shell resolves a real file with both in its path to an item id list
+        let dir = std::env::temp_dir().join(format!("aura reveal {}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("c,d.txt");
+        fs::write(&file, "").unwrap();
+        let wide = wide_path(&file);
+        let item = unsafe { ILCreateFromPathW(wide.as_ptr()) };
+        let resolved = !item.is_null();
+        if resolved {
+            unsafe { ILFree(item) };
+        }
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(resolved);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn show_items_uri_escapes_commas() {
+        assert_eq!(
+            show_items_uri(Path::new("/home/me/a,b c.txt")).as_deref(),
+            Some("file:///home/me/a%2Cb%20c.txt")
+        );
+        assert_eq!(show_items_uri(Path::new("relative.txt")), None);
+    }
+}
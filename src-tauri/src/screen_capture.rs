@@ -0,0 +1,87 @@
+use std::io::Cursor;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use tauri::{AppHandle, Monitor, Window};
+use xcap::image::ImageFormat;
+
+use crate::monitor;
+
+// Screen recording must be granted in System Settings before captures contain anything
+#[cfg(target_os = "macos")]
+fn check_permission() -> Result<(), String> {
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+        fn CGRequestScreenCaptureAccess() -> bool;
+    }
+
+    if unsafe { CGPreflightScreenCaptureAccess() } {
+        return Ok(());
+    }
+    // Adds Aura to the Screen Recording list so the user only has to flip the switch
+    unsafe { CGRequestScreenCaptureAccess() };
+    Err(concat!(
+        "Screen recording permission is required. Enable Aura under System Settings > ",
+        "Privacy & Security > Screen Recording, then restart Aura"
+    )
+    .to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn check_permission() -> Result<(), String> {
+    Ok(())
+}
+
+// Monitor at the get_monitors index, or the one under the cursor when no index is given
+fn target_monitor(window: &Window, index: Option<usize>) -> Result<Monitor, String> {
+    if let Some(index) = index {
+        return window
+            .available_monitors()
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .nth(index)
+            .ok_or_else(|| format!("No monitor at index {}", index));
+    }
+
+    if let Some(monitor) =
+        monitor::cursor_position().and_then(|cursor| monitor::monitor_at(window, cursor))
+    {
+        return Ok(monitor);
+    }
+    window
+        .primary_monitor()
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| "No monitor available".to_string())
+}
+
+// Capture a monitor and return it as a base64-encoded PNG
+#[tauri::command]
+pub async fn capture_screen(
+    app: AppHandle,
+    monitor_index: Option<usize>,
+) -> Result<String, String> {
+    check_permission()?;
+
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    let monitor = target_monitor(&window, monitor_index)?;
+
+    // xcap identifies displays by a point inside them, in points on macOS and pixels elsewhere
+    let bounds = monitor::Rect::from_monitor(&monitor);
+    let mut x = bounds.x + bounds.width as i32 / 2;
+    let mut y = bounds.y + bounds.height as i32 / 2;
+    if cfg!(target_os = "macos") {
+        x = (x as f64 / monitor.scale_factor()).round() as i32;
+        y = (y as f64 / monitor.scale_factor()).round() as i32;
+    }
+
+    let image = xcap::Monitor::from_point(x, y)
+        .and_then(|display| display.capture_image())
+        .map_err(|err| format!("Failed to capture screen: {}", err))?;
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|err| format!("Failed to encode screenshot: {}", err))?;
+    Ok(STANDARD.encode(png))
+}
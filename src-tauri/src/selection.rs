@@ -0,0 +1,127 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use arboard::{Clipboard, ImageData};
+use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{fade, hide_on_blur, monitor, paste};
+
+// How long the other app gets to put its selection on the clipboard
+const COPY_TIMEOUT: Duration = Duration::from_millis(400);
+const COPY_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+// Modifier and key of the platform copy shortcut
+#[cfg(target_os = "macos")]
+const COPY_MODIFIER: Key = Key::Meta;
+#[cfg(not(target_os = "macos"))]
+const COPY_MODIFIER: Key = Key::Control;
+#[cfg(target_os = "windows")]
+const COPY_KEY: Key = Key::C;
+#[cfg(not(target_os = "windows"))]
+const COPY_KEY: Key = Key::Unicode('c');
+
+// Modifiers the user may still be holding from the capture shortcut
+const HELD_MODIFIERS: [Key; 4] = [Key::Shift, Key::Alt, Key::Control, Key::Meta];
+
+// Text selected in the app in front when the capture shortcut was pressed; empty when nothing
+// was selected
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectionCaptured {
+    pub text: String,
+}
+
+// What the user had on the clipboard before the capture borrowed it
+enum SavedClipboard {
+    Text(String),
+    Image(ImageData<'static>),
+    Empty,
+}
+
+fn save(clipboard: &mut Clipboard) -> SavedClipboard {
+    if let Ok(text) = clipboard.get_text() {
+        return SavedClipboard::Text(text);
+    }
+    match clipboard.get_image() {
+        Ok(image) => SavedClipboard::Image(image),
+        Err(_) => SavedClipboard::Empty,
+    }
+}
+
+fn restore(clipboard: &mut Clipboard, saved: SavedClipboard) -> Result<(), arboard::Error> {
+    match saved {
+        SavedClipboard::Text(text) => clipboard.set_text(text),
+        SavedClipboard::Image(image) => clipboard.set_image(image),
+        SavedClipboard::Empty => clipboard.clear(),
+    }
+}
+
+// Release the shortcut's modifiers first, or Ctrl+Shift+C would reach the app instead of Ctrl+C
+fn send_copy() -> Result<(), String> {
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|err| format!("Failed to start keyboard input: {}", err))?;
+    for modifier in HELD_MODIFIERS {
+        enigo
+            .key(modifier, Direction::Release)
+            .map_err(|err| format!("Failed to release modifier: {}", err))?;
+    }
+    enigo
+        .key(COPY_MODIFIER, Direction::Press)
+        .and_then(|_| enigo.key(COPY_KEY, Direction::Click))
+        .and_then(|_| enigo.key(COPY_MODIFIER, Direction::Release))
+        .map_err(|err| format!("Failed to send copy keystroke: {}", err))
+}
+
+// Copy the selection of the app in front and read it back. The clipboard is cleared first so
+// an empty selection is not mistaken for what was already there, and put back afterwards
+fn copy_selection() -> Result<String, String> {
+    paste::check_permission()?;
+    let mut clipboard =
+        Clipboard::new().map_err(|err| format!("Failed to open clipboard: {}", err))?;
+    let saved = save(&mut clipboard);
+    clipboard
+        .clear()
+        .map_err(|err| format!("Failed to clear clipboard: {}", err))?;
+
+    let copied = send_copy().map(|_| {
+        let started = Instant::now();
+        loop {
+            match clipboard.get_text() {
+                Ok(text) if !text.is_empty() => return text,
+                _ if started.elapsed() >= COPY_TIMEOUT => return String::new(),
+                _ => thread::sleep(COPY_POLL_INTERVAL),
+            }
+        }
+    });
+
+    restore(&mut clipboard, saved)
+        .unwrap_or_else(|err| tracing::error!("Failed to restore clipboard: {}", err));
+    copied
+}
+
+// Grab the current selection, then show the window and emit selection-captured. Runs on its
+// own thread since the copy takes a moment to land on the clipboard
+pub fn capture(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let text = copy_selection().unwrap_or_else(|err| {
+            tracing::error!("Failed to capture selection: {}", err);
+            String::new()
+        });
+
+        let window = match crate::main_window(&app) {
+            Some(window) => window,
+            None => return,
+        };
+        if !fade::is_shown(&window).unwrap_or(false) {
+            monitor::prepare_to_show(&window);
+            hide_on_blur::mark_shown(&app);
+            fade::show(&window)
+                .unwrap_or_else(|err| tracing::error!("Failed to show window: {}", err));
+        }
+        window
+            .emit("selection-captured", SelectionCaptured { text })
+            .unwrap_or_else(|err| tracing::error!("Failed to emit captured selection: {}", err));
+    });
+}
@@ -0,0 +1,322 @@
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::autostart;
+use crate::close_behavior::CloseBehavior;
+use crate::double_tap::{self, DoubleTapActivation};
+use crate::hide_on_blur;
+use crate::hide_shortcut::{self, DEFAULT_HIDE_SHORTCUT};
+use crate::opacity;
+use crate::push_to_talk::{self, DEFAULT_PUSH_TO_TALK_SHORTCUT};
+use crate::shortcut_actions::{self, ActionShortcuts};
+use crate::shortcut_registry;
+use crate::snap;
+use crate::taskbar;
+use crate::toggle_shortcut::{self, DEFAULT_TOGGLE_SHORTCUT};
+use crate::tray_click::{self, TrayLeftClickAction};
+use crate::window_controls;
+use crate::window_effect::{self, WindowEffect};
+use crate::window_mode::{self, WindowMode, WindowSize};
+use crate::zoom;
+
+// File under app_data_dir() holding user preferences
+const SETTINGS_FILE: &str = "settings.json";
+
+// User preferences persisted across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    pub always_on_top: bool,
+    pub window_opacity: f64,
+    pub hide_on_blur: bool,
+    pub window_mode: WindowMode,
+    pub compact_size: WindowSize,
+    pub expanded_size: WindowSize,
+    pub snap_margin: f64,
+    pub snap_shortcuts: bool,
+    pub follow_cursor_monitor: bool,
+    pub preferred_monitor: Option<String>,
+    pub window_effect: WindowEffect,
+    pub push_to_talk_shortcut: String,
+    pub push_to_talk_enabled: bool,
+    pub close_behavior: CloseBehavior,
+    pub minimize_to_tray: bool,
+    pub auto_hide_minutes: u64,
+    pub animations_enabled: Option<bool>,
+    pub zoom: f64,
+    pub toggle_shortcut: String,
+    pub hide_shortcut: String,
+    pub action_shortcuts: ActionShortcuts,
+    pub double_tap_activation: DoubleTapActivation,
+    pub input_device: Option<String>,
+    pub disabled_shortcuts: BTreeSet<String>,
+    pub hotkeys_paused: bool,
+    pub toggle_debounce_ms: u64,
+    pub fade_duration_ms: u64,
+    pub tray_left_click_action: TrayLeftClickAction,
+    pub launch_at_login: bool,
+    pub skip_taskbar: bool,
+    pub decorations: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            always_on_top: true,
+            window_opacity: 1.0,
+            hide_on_blur: false,
+            window_mode: WindowMode::Expanded,
+            compact_size: WindowSize {
+                width: 600.0,
+                height: 72.0,
+            },
+            expanded_size: WindowSize {
+                width: 600.0,
+                height: 400.0,
+            },
+            snap_margin: 16.0,
+            snap_shortcuts: false,
+            // Opt-in, so the remembered window position is kept by default
+            follow_cursor_monitor: false,
+            preferred_monitor: None,
+            window_effect: WindowEffect::None,
+            push_to_talk_shortcut: DEFAULT_PUSH_TO_TALK_SHORTCUT.to_string(),
+            // Off until the user turns it on, so no keyboard hook is installed at launch
+            push_to_talk_enabled: false,
+            close_behavior: CloseBehavior::Hide,
+            minimize_to_tray: false,
+            auto_hide_minutes: 0,
+            animations_enabled: None,
+            zoom: 1.0,
+            toggle_shortcut: DEFAULT_TOGGLE_SHORTCUT.to_string(),
+            hide_shortcut: DEFAULT_HIDE_SHORTCUT.to_string(),
+            action_shortcuts: ActionShortcuts::default(),
+            double_tap_activation: DoubleTapActivation::default(),
+            input_device: None,
+            disabled_shortcuts: BTreeSet::new(),
+            hotkeys_paused: false,
+            toggle_debounce_ms: 250,
+            fade_duration_ms: 150,
+            tray_left_click_action: TrayLeftClickAction::ToggleWindow,
+            launch_at_login: false,
+            // The Dock icon stays on macOS unless the user hides it
+            skip_taskbar: cfg!(not(target_os = "macos")),
+            // The window is borderless with its own titlebar unless the user wants the native one
+            decorations: false,
+        }
+    }
+}
+
+// Settings shared between commands, tray handlers and window events
+pub struct SettingsState(pub Mutex<Settings>);
+
+fn settings_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path_resolver()
+        .app_data_dir()
+        .map(|dir| dir.join(SETTINGS_FILE))
+}
+
+// Read settings from disk, keeping defaults for anything missing or malformed
+pub fn load(app: &AppHandle) -> Settings {
+    let contents = match settings_path(app).and_then(|path| fs::read_to_string(path).ok()) {
+        Some(contents) => contents,
+        None => return Settings::default(),
+    };
+    match serde_json::from_str(&contents) {
+        Ok(Value::Object(stored)) => clamp_values(merge_with_defaults(migrate(stored))),
+        _ => {
+            tracing::warn!("Settings file is corrupt, using defaults");
+            Settings::default()
+        }
+    }
+}
+
+// Carry renamed fields over to their current names. Only an explicit opt-in to centering
+// carries over, since following the cursor is off by default
+fn migrate(mut stored: Map<String, Value>) -> Map<String, Value> {
+    if stored.remove("centerOnActiveMonitor") == Some(Value::Bool(true)) {
+        stored
+            .entry("followCursorMonitor".to_string())
+            .or_insert(Value::Bool(true));
+    }
+    stored
+}
+
+// Pull hand-edited values back into range, e.g. an opacity that would make the window invisible
+// or a tray action this platform cannot carry out
+fn clamp_values(mut settings: Settings) -> Settings {
+    settings.window_opacity = opacity::clamp(settings.window_opacity);
+    if !tray_click::is_supported(settings.tray_left_click_action) {
+        settings.tray_left_click_action = TrayLeftClickAction::ToggleWindow;
+    }
+    settings
+}
+
+// Overlay stored fields on the defaults one at a time so a bad value only loses itself
+fn merge_with_defaults(stored: Map<String, Value>) -> Settings {
+    let mut merged = match serde_json::to_value(Settings::default()) {
+        Ok(Value::Object(defaults)) => defaults,
+        _ => return Settings::default(),
+    };
+
+    for (key, value) in stored {
+        let previous = merged.insert(key.clone(), value);
+        if serde_json::from_value::<Settings>(Value::Object(merged.clone())).is_err() {
+            tracing::warn!("Ignoring invalid setting '{}'", key);
+            match previous {
+                Some(previous) => merged.insert(key, previous),
+                None => merged.remove(&key),
+            };
+        }
+    }
+    serde_json::from_value(Value::Object(merged)).unwrap_or_default()
+}
+
+// Write settings to disk through a temporary file, so a crash mid-write leaves the previous
+// file in place rather than truncated JSON that load would replace with the defaults
+pub fn save(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = settings_path(app).ok_or("App data directory is unavailable")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(settings).map_err(|err| err.to_string())?;
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", SETTINGS_FILE, process::id()));
+    let result = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, &path));
+    if let Err(err) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(err.to_string());
+    }
+    Ok(())
+}
+
+// Snapshot of the current settings
+pub fn current(app: &AppHandle) -> Settings {
+    app.state::<SettingsState>().0.lock().unwrap().clone()
+}
+
+// Modify the shared settings and persist the result
+pub fn update<F: FnOnce(&mut Settings)>(app: &AppHandle, change: F) -> Result<(), String> {
+    let state = app.state::<SettingsState>();
+    let mut settings = state.0.lock().unwrap();
+    change(&mut settings);
+    save(app, &settings)
+}
+
+// Current settings for the settings UI
+#[tauri::command]
+pub fn get_settings(app: AppHandle) -> Settings {
+    current(&app)
+}
+
+// Route changed preferences with live state through their own setters so the window and tray
+// follow. Each setter persists its own field as it succeeds
+fn apply_changes(
+    app: &AppHandle,
+    previous: &Settings,
+    settings: &mut Settings,
+) -> Result<(), String> {
+    if settings.always_on_top != previous.always_on_top {
+        crate::apply_always_on_top(app, settings.always_on_top)?;
+    }
+    if settings.window_opacity != previous.window_opacity && opacity::SUPPORTED {
+        opacity::set(app, settings.window_opacity).map_err(|err| err.to_string())?;
+    }
+    if settings.window_effect != previous.window_effect {
+        window_effect::set(app, settings.window_effect).map_err(|err| err.to_string())?;
+    }
+    if settings.zoom != previous.zoom {
+        settings.zoom = zoom::set(app, settings.zoom)?;
+    }
+    if settings.window_mode != previous.window_mode {
+        window_mode::set(app, settings.window_mode)?;
+    }
+    if settings.snap_shortcuts != previous.snap_shortcuts {
+        snap::apply(app, settings.snap_shortcuts)?;
+    }
+    if settings.hide_on_blur != previous.hide_on_blur {
+        hide_on_blur::apply(app, settings.hide_on_blur)?;
+    }
+    if settings.launch_at_login != previous.launch_at_login {
+        autostart::apply(app, settings.launch_at_login)?;
+    }
+    if settings.skip_taskbar != previous.skip_taskbar {
+        taskbar::apply(app, settings.skip_taskbar)?;
+    }
+    if settings.decorations != previous.decorations {
+        window_controls::apply_decorations(app, settings.decorations)?;
+    }
+    if settings.push_to_talk_shortcut != previous.push_to_talk_shortcut {
+        push_to_talk::apply(app, settings.push_to_talk_shortcut.clone())?;
+    }
+    if settings.push_to_talk_enabled != previous.push_to_talk_enabled {
+        push_to_talk::apply_enabled(app, settings.push_to_talk_enabled)?;
+    }
+    if settings.toggle_shortcut != previous.toggle_shortcut {
+        toggle_shortcut::apply(app, settings.toggle_shortcut.clone())
+            .map_err(|err| err.to_string())?;
+    }
+    if settings.hide_shortcut != previous.hide_shortcut {
+        hide_shortcut::apply(app, settings.hide_shortcut.clone()).map_err(|err| err.to_string())?;
+    }
+    if settings.action_shortcuts != previous.action_shortcuts {
+        shortcut_actions::apply(app, &settings.action_shortcuts);
+    }
+    for id in settings
+        .disabled_shortcuts
+        .symmetric_difference(&previous.disabled_shortcuts)
+    {
+        // Ids that are not registered yet pick the flag up when they are
+        let enabled = !settings.disabled_shortcuts.contains(id);
+        if let Err(err) = shortcut_registry::set_enabled(app, id, enabled) {
+            tracing::error!("Failed to update shortcut '{}': {}", id, err);
+        }
+    }
+    if settings.hotkeys_paused != previous.hotkeys_paused {
+        shortcut_registry::set_paused(app, settings.hotkeys_paused)?;
+    }
+    if settings.double_tap_activation != previous.double_tap_activation {
+        double_tap::apply(app, settings.double_tap_activation.clone())?;
+    }
+    Ok(())
+}
+
+// Replace the settings with the frontend's copy and persist them
+#[tauri::command]
+pub fn update_settings(app: AppHandle, settings: Settings) -> Result<Settings, String> {
+    let previous = current(&app);
+    if !tray_click::is_supported(settings.tray_left_click_action) {
+        return Err("The tray menu cannot open on left click on this platform".to_string());
+    }
+    let mut settings = clamp_values(settings);
+
+    if let Err(err) = apply_changes(&app, &previous, &mut settings) {
+        // The setters that ran already saved their fields, so the UI is sent what took effect
+        app.emit_all("settings-changed", current(&app))
+            .unwrap_or_else(|err| tracing::error!("Failed to emit settings-changed: {}", err));
+        return Err(err);
+    }
+
+    {
+        let state = app.state::<SettingsState>();
+        let mut stored = state.0.lock().unwrap();
+        *stored = settings.clone();
+        save(&app, &stored)?;
+    }
+    // Global listeners run inside emit_all and read the settings, so the lock is released first
+    app.emit_all("settings-changed", settings.clone())
+        .map_err(|err| err.to_string())?;
+    Ok(settings)
+}
@@ -0,0 +1,28 @@
+use tauri::{AppHandle, Manager, WindowBuilder, WindowUrl};
+
+// Label of the settings window
+pub const SETTINGS_WINDOW: &str = "settings";
+
+// Show the settings window, creating it on first use
+pub fn open(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_window(SETTINGS_WINDOW) {
+        window.show()?;
+        return window.set_focus();
+    }
+
+    // Closing the window destroys it; it is recreated the next time settings are opened
+    WindowBuilder::new(app, SETTINGS_WINDOW, WindowUrl::App("settings.html".into()))
+        .title("Aura Settings")
+        .inner_size(600.0, 500.0)
+        .resizable(false)
+        .center()
+        .focused(true)
+        .build()?;
+    Ok(())
+}
+
+// Open the settings window from the frontend
+#[tauri::command]
+pub async fn open_settings(app: AppHandle) -> Result<(), String> {
+    open(&app).map_err(|err| err.to_string())
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{fade, hide_on_blur, monitor, selection, shortcut_registry};
+
+// Default accelerators; Ctrl+Alt alone is AltGr on many European layouts and Cmd+Option+D
+// already toggles the Dock, so both platforms add Shift
+#[cfg(target_os = "macos")]
+const DEFAULT_DICTATION_SHORTCUT: &str = "Cmd+Option+Shift+D";
+#[cfg(target_os = "macos")]
+const DEFAULT_SCREENSHOT_SHORTCUT: &str = "Cmd+Option+Shift+S";
+#[cfg(target_os = "macos")]
+const DEFAULT_CLIPBOARD_SHORTCUT: &str = "Cmd+Option+Shift+V";
+#[cfg(target_os = "macos")]
+const DEFAULT_SELECTION_SHORTCUT: &str = "Cmd+Option+Shift+C";
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_DICTATION_SHORTCUT: &str = "Ctrl+Alt+Shift+D";
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_SCREENSHOT_SHORTCUT: &str = "Ctrl+Alt+Shift+S";
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_CLIPBOARD_SHORTCUT: &str = "Ctrl+Alt+Shift+V";
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_SELECTION_SHORTCUT: &str = "Ctrl+Alt+Shift+C";
+
+// Named actions the frontend carries out when their shortcut fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShortcutAction {
+    StartDictation,
+    CaptureScreenshot,
+    CaptureClipboard,
+    CaptureSelection,
+}
+
+impl ShortcutAction {
+    fn id(self) -> &'static str {
+        match self {
+            ShortcutAction::StartDictation => "start-dictation",
+            ShortcutAction::CaptureScreenshot => "capture-screenshot",
+            ShortcutAction::CaptureClipboard => "capture-clipboard",
+            ShortcutAction::CaptureSelection => "capture-selection",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            ShortcutAction::StartDictation => "Show the assistant and start dictation",
+            ShortcutAction::CaptureScreenshot => "Capture a screenshot for the assistant",
+            ShortcutAction::CaptureClipboard => "Send the clipboard to the assistant",
+            ShortcutAction::CaptureSelection => "Send the selected text to the assistant",
+        }
+    }
+
+    // Dictation needs the window; captures happen in the background so the window stays out
+    // of the screenshot and the user's focus is kept
+    fn shows_window(self) -> bool {
+        matches!(self, ShortcutAction::StartDictation)
+    }
+}
+
+// Accelerator for each named action, stored in the settings file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ActionShortcuts {
+    pub start_dictation: String,
+    pub capture_screenshot: String,
+    pub capture_clipboard: String,
+    pub capture_selection: String,
+}
+
+impl Default for ActionShortcuts {
+    fn default() -> Self {
+        ActionShortcuts {
+            start_dictation: DEFAULT_DICTATION_SHORTCUT.to_string(),
+            capture_screenshot: DEFAULT_SCREENSHOT_SHORTCUT.to_string(),
+            capture_clipboard: DEFAULT_CLIPBOARD_SHORTCUT.to_string(),
+            capture_selection: DEFAULT_SELECTION_SHORTCUT.to_string(),
+        }
+    }
+}
+
+impl ActionShortcuts {
+    fn bindings(&self) -> [(&str, ShortcutAction); 4] {
+        [
+            (&self.start_dictation, ShortcutAction::StartDictation),
+            (&self.capture_screenshot, ShortcutAction::CaptureScreenshot),
+            (&self.capture_clipboard, ShortcutAction::CaptureClipboard),
+            (&self.capture_selection, ShortcutAction::CaptureSelection),
+        ]
+    }
+}
+
+// Action name sent to the main window when its shortcut fires
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutActionPayload {
+    pub action: ShortcutAction,
+}
+
+fn run(app: &AppHandle, action: ShortcutAction) {
+    // The selection has to be copied before the window takes focus, and the text itself is
+    // sent rather than the action name
+    if action == ShortcutAction::CaptureSelection {
+        selection::capture(app);
+        return;
+    }
+    let window = match crate::main_window(app) {
+        Some(window) => window,
+        None => return,
+    };
+    if action.shows_window() && !fade::is_shown(&window).unwrap_or(false) {
+        monitor::prepare_to_show(&window);
+        hide_on_blur::mark_shown(app);
+        fade::show(&window).unwrap_or_else(|err| tracing::error!("Failed to show window: {}", err));
+    }
+    window
+        .emit("shortcut-action", ShortcutActionPayload { action })
+        .unwrap_or_else(|err| tracing::error!("Failed to emit shortcut action: {}", err));
+}
+
+// Bind every action to its accelerator; failures are kept in the shortcut registry
+pub fn register(app: &AppHandle, shortcuts: &ActionShortcuts) {
+    for (accelerator, action) in shortcuts.bindings() {
+        // An empty accelerator turns the action's shortcut off
+        if accelerator.is_empty() {
+            shortcut_registry::unregister(app, action.id());
+            continue;
+        }
+        let app_handle = app.clone();
+        let _ = shortcut_registry::register(
+            app,
+            action.id(),
+            accelerator,
+            action.description(),
+            move || run(&app_handle, action),
+        );
+    }
+}
+
+// Move the actions to new accelerators after a settings change
+pub fn apply(app: &AppHandle, shortcuts: &ActionShortcuts) {
+    register(app, shortcuts);
+    shortcut_registry::notify_failures(app);
+}
@@ -0,0 +1,616 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, GlobalShortcutManager, Manager};
+
+use crate::settings;
+
+type Handler = Arc<dyn Fn() + Send + Sync>;
+
+// Tray menu item that pauses every global shortcut
+pub const PAUSE_TRAY_ITEM_ID: &str = "pause_hotkeys";
+
+struct Entry {
+    accelerator: String,
+    description: String,
+    active: bool,
+    // Turned off by the user; the entry keeps its other state and binds again when turned on
+    enabled: bool,
+    error: Option<String>,
+    handler: Handler,
+}
+
+// Every global accelerator Aura binds, keyed by id; all registrations go through here. While
+// paused nothing is bound, without changing any entry
+#[derive(Default)]
+pub struct ShortcutRegistry {
+    entries: Mutex<BTreeMap<String, Entry>>,
+    paused: AtomicBool,
+    // Error of the most recent failed bind, for the settings banner
+    last_error: Mutex<Option<String>>,
+}
+
+// How long capture mode may hold the shortcuts back if the frontend never ends it
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(15);
+
+// Capture mode for recording a new binding; while active no accelerator is bound, so the keys
+// reach the webview. The generation tells a timeout whether its capture is still the current one
+#[derive(Default)]
+pub struct ShortcutCapture(Mutex<CaptureState>);
+
+#[derive(Default)]
+struct CaptureState {
+    active: bool,
+    generation: u64,
+}
+
+fn capturing(app: &AppHandle) -> bool {
+    app.state::<ShortcutCapture>().0.lock().unwrap().active
+}
+
+fn paused(app: &AppHandle) -> bool {
+    app.state::<ShortcutRegistry>()
+        .paused
+        .load(Ordering::SeqCst)
+}
+
+// Aura as a whole is paused, see pause.rs
+fn suspended(app: &AppHandle) -> bool {
+    crate::pause::is_paused(app)
+}
+
+// Capture mode, the hotkey pause and Aura's pause all keep every accelerator released
+fn held_back(app: &AppHandle) -> bool {
+    capturing(app) || paused(app) || suspended(app)
+}
+
+// Every registration outcome plus the most recent error, cleared once nothing is failing
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutStatus {
+    pub registered: Vec<String>,
+    pub shortcuts: Vec<ShortcutRegistration>,
+    pub last_error: Option<String>,
+}
+
+// A registry entry as shown by the settings screen
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredShortcut {
+    pub id: String,
+    pub accelerator: String,
+    pub active: bool,
+    pub enabled: bool,
+    pub description: String,
+}
+
+// Outcome of binding one global accelerator
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutRegistration {
+    pub accelerator: String,
+    pub registered: bool,
+    pub error: Option<String>,
+}
+
+// The manager is called without the registry locked, since shortcut handlers and window
+// events run on the event loop thread the manager waits on
+fn bind(app: &AppHandle, accelerator: &str, handler: &Handler) -> Result<(), String> {
+    // Entries changed while held back are only recorded; resuming binds them
+    if held_back(app) {
+        return Ok(());
+    }
+    let handler = handler.clone();
+    let app_handle = app.clone();
+    app.global_shortcut_manager()
+        .register(accelerator, move || {
+            // An accelerator the OS failed to release must still do nothing while Aura is paused
+            if !suspended(&app_handle) {
+                crate::active_window::capture(&app_handle);
+                handler()
+            }
+        })
+        .map_err(|err| {
+            tracing::error!("Failed to register shortcut '{}': {}", accelerator, err);
+            *app.state::<ShortcutRegistry>().last_error.lock().unwrap() = Some(format!(
+                "Shortcut '{}' could not be registered: {}",
+                accelerator, err
+            ));
+            err.to_string()
+        })?;
+    tracing::info!("Registered shortcut '{}'", accelerator);
+    Ok(())
+}
+
+fn release(app: &AppHandle, accelerator: &str) {
+    if held_back(app) {
+        return;
+    }
+    match app.global_shortcut_manager().unregister(accelerator) {
+        Ok(()) => tracing::info!("Unregistered shortcut '{}'", accelerator),
+        Err(err) => tracing::error!("Failed to unregister shortcut '{}': {}", accelerator, err),
+    }
+}
+
+// Bind an accelerator under an id. An id that is already bound keeps its old keys until the
+// new ones succeed; a failed first attempt stays listed as inactive with its error
+pub fn register<F: Fn() + Send + Sync + 'static>(
+    app: &AppHandle,
+    id: &str,
+    accelerator: &str,
+    description: &str,
+    handler: F,
+) -> Result<(), String> {
+    let registry = app.state::<ShortcutRegistry>();
+    let existing = registry
+        .entries
+        .lock()
+        .unwrap()
+        .get(id)
+        .map(|entry| (entry.accelerator.clone(), entry.active, entry.enabled));
+    let enabled = match &existing {
+        Some((_, _, enabled)) => *enabled,
+        None => !settings::current(app).disabled_shortcuts.contains(id),
+    };
+    let handler: Handler = Arc::new(handler);
+
+    // A disabled id is only recorded; nothing of it is bound
+    if !enabled {
+        registry.entries.lock().unwrap().insert(
+            id.to_string(),
+            Entry {
+                accelerator: accelerator.to_string(),
+                description: description.to_string(),
+                active: true,
+                enabled,
+                error: None,
+                handler,
+            },
+        );
+        return Ok(());
+    }
+
+    let previous = existing.map(|(accelerator, active, _)| (accelerator, active));
+    if let Some((previous_accelerator, true)) = &previous {
+        if previous_accelerator == accelerator {
+            return Ok(());
+        }
+    }
+
+    let result = bind(app, accelerator, &handler);
+    let keep_previous = matches!(previous, Some((_, true)));
+    match &result {
+        Ok(()) => {
+            if let Some((previous_accelerator, true)) = &previous {
+                release(app, previous_accelerator);
+            }
+        }
+        Err(_) if keep_previous => return result,
+        Err(_) => {}
+    }
+
+    registry.entries.lock().unwrap().insert(
+        id.to_string(),
+        Entry {
+            accelerator: accelerator.to_string(),
+            description: description.to_string(),
+            active: result.is_ok(),
+            enabled,
+            error: result.as_ref().err().cloned(),
+            handler,
+        },
+    );
+    result
+}
+
+// Release an id's accelerator and drop it from the registry
+pub fn unregister(app: &AppHandle, id: &str) -> Option<String> {
+    let entry = app
+        .state::<ShortcutRegistry>()
+        .entries
+        .lock()
+        .unwrap()
+        .remove(id)?;
+    if entry.active && entry.enabled {
+        release(app, &entry.accelerator);
+    }
+    Some(entry.accelerator)
+}
+
+// Release an id's accelerator but keep it listed so activate can bind it again
+pub fn deactivate(app: &AppHandle, id: &str) {
+    let accelerator = {
+        let registry = app.state::<ShortcutRegistry>();
+        let mut entries = registry.entries.lock().unwrap();
+        match entries.get_mut(id) {
+            Some(entry) if entry.active => {
+                entry.active = false;
+                if !entry.enabled {
+                    return;
+                }
+                entry.accelerator.clone()
+            }
+            _ => return,
+        }
+    };
+    release(app, &accelerator);
+}
+
+// Bind a deactivated id again; false when the id is unknown
+pub fn activate(app: &AppHandle, id: &str) -> bool {
+    let (accelerator, handler) = {
+        let registry = app.state::<ShortcutRegistry>();
+        let mut entries = registry.entries.lock().unwrap();
+        match entries.get_mut(id) {
+            Some(entry) if entry.active => return true,
+            Some(entry) if !entry.enabled => {
+                entry.active = true;
+                return true;
+            }
+            Some(entry) => (entry.accelerator.clone(), entry.handler.clone()),
+            None => return false,
+        }
+    };
+    let result = bind(app, &accelerator, &handler);
+    if let Some(entry) = app
+        .state::<ShortcutRegistry>()
+        .entries
+        .lock()
+        .unwrap()
+        .get_mut(id)
+    {
+        entry.active = result.is_ok();
+        entry.error = result.err();
+    }
+    true
+}
+
+// Accelerator currently assigned to an id
+pub fn accelerator(app: &AppHandle, id: &str) -> Option<String> {
+    let registry = app.state::<ShortcutRegistry>();
+    let entries = registry.entries.lock().unwrap();
+    entries.get(id).map(|entry| entry.accelerator.clone())
+}
+
+// Number of ids starting with a prefix
+pub fn count(app: &AppHandle, prefix: &str) -> usize {
+    let registry = app.state::<ShortcutRegistry>();
+    let entries = registry.entries.lock().unwrap();
+    entries.keys().filter(|id| id.starts_with(prefix)).count()
+}
+
+// Bind every active or failed accelerator again, e.g. after the OS dropped them across sleep;
+// ids deactivated on purpose stay released
+pub fn refresh(app: &AppHandle) {
+    if held_back(app) {
+        return;
+    }
+    let wanted: Vec<(String, String, Handler)> = {
+        let registry = app.state::<ShortcutRegistry>();
+        let entries = registry.entries.lock().unwrap();
+        entries
+            .iter()
+            .filter(|(_, entry)| entry.enabled && (entry.active || entry.error.is_some()))
+            .map(|(id, entry)| (id.clone(), entry.accelerator.clone(), entry.handler.clone()))
+            .collect()
+    };
+
+    let mut failed = false;
+    for (id, accelerator, handler) in wanted {
+        // Tauri may still think a binding the OS dropped is held, which would block registering
+        let mut shortcut_manager = app.global_shortcut_manager();
+        if shortcut_manager
+            .is_registered(&accelerator)
+            .unwrap_or(false)
+        {
+            let _ = shortcut_manager.unregister(&accelerator);
+        }
+        let result = bind(app, &accelerator, &handler);
+        failed |= result.is_err();
+        if let Some(entry) = app
+            .state::<ShortcutRegistry>()
+            .entries
+            .lock()
+            .unwrap()
+            .get_mut(&id)
+        {
+            entry.active = result.is_ok();
+            entry.error = result.err();
+        }
+    }
+    if failed {
+        notify_failures(app);
+    }
+}
+
+// Release every accelerator on the way out
+pub fn unregister_all(app: &AppHandle) {
+    for entry in app
+        .state::<ShortcutRegistry>()
+        .entries
+        .lock()
+        .unwrap()
+        .values_mut()
+    {
+        entry.active = false;
+    }
+    app.global_shortcut_manager()
+        .unregister_all()
+        .unwrap_or_else(|err| tracing::error!("Failed to unregister shortcuts: {}", err));
+}
+
+// Release every accelerator currently bound, keeping the entries as they are; returns the
+// accelerators that could not be released
+fn release_bound(app: &AppHandle) -> Vec<String> {
+    let bound: Vec<String> = {
+        let registry = app.state::<ShortcutRegistry>();
+        let entries = registry.entries.lock().unwrap();
+        entries
+            .values()
+            .filter(|entry| entry.active && entry.enabled)
+            .map(|entry| entry.accelerator.clone())
+            .collect()
+    };
+    let mut shortcut_manager = app.global_shortcut_manager();
+    let mut failed = Vec::new();
+    for accelerator in bound {
+        if let Err(err) = shortcut_manager.unregister(&accelerator) {
+            tracing::error!("Failed to suspend shortcut '{}': {}", accelerator, err);
+            failed.push(accelerator);
+        }
+    }
+    failed
+}
+
+// Release every bound accelerator until end_capture or the timeout; calling it again while
+// capturing only restarts the timeout
+pub fn begin_capture(app: &AppHandle) {
+    let (was_active, generation) = {
+        let capture = app.state::<ShortcutCapture>();
+        let mut state = capture.0.lock().unwrap();
+        let was_active = state.active;
+        state.active = true;
+        state.generation += 1;
+        (was_active, state.generation)
+    };
+
+    if !was_active && !paused(app) && !suspended(app) {
+        release_bound(app);
+    }
+
+    let app = app.clone();
+    thread::spawn(move || {
+        thread::sleep(CAPTURE_TIMEOUT);
+        let expired = {
+            let capture = app.state::<ShortcutCapture>();
+            let state = capture.0.lock().unwrap();
+            state.active && state.generation == generation
+        };
+        if expired {
+            tracing::warn!("Shortcut capture was not ended, restoring shortcuts");
+            end_capture(&app);
+        }
+    });
+}
+
+// Bind every accelerator again after capture mode; a no-op when not capturing
+pub fn end_capture(app: &AppHandle) {
+    {
+        let capture = app.state::<ShortcutCapture>();
+        let mut state = capture.0.lock().unwrap();
+        if !state.active {
+            return;
+        }
+        state.active = false;
+        state.generation += 1;
+    }
+    refresh(app);
+}
+
+// Turn one id on or off and persist the choice; an id turned back on binds again if its owner
+// still wants it
+pub fn set_enabled(app: &AppHandle, id: &str, enabled: bool) -> Result<(), String> {
+    let (accelerator, active, handler) = {
+        let registry = app.state::<ShortcutRegistry>();
+        let mut entries = registry.entries.lock().unwrap();
+        let entry = entries
+            .get_mut(id)
+            .ok_or_else(|| format!("Unknown shortcut '{}'", id))?;
+        if entry.enabled == enabled {
+            return Ok(());
+        }
+        entry.enabled = enabled;
+        entry.error = None;
+        (
+            entry.accelerator.clone(),
+            entry.active,
+            entry.handler.clone(),
+        )
+    };
+
+    let result = if !active {
+        Ok(())
+    } else if enabled {
+        bind(app, &accelerator, &handler)
+    } else {
+        release(app, &accelerator);
+        Ok(())
+    };
+    if let Err(err) = &result {
+        if let Some(entry) = app
+            .state::<ShortcutRegistry>()
+            .entries
+            .lock()
+            .unwrap()
+            .get_mut(id)
+        {
+            entry.active = false;
+            entry.error = Some(err.clone());
+        }
+    }
+
+    settings::update(app, |settings| {
+        if enabled {
+            settings.disabled_shortcuts.remove(id);
+        } else {
+            settings.disabled_shortcuts.insert(id.to_string());
+        }
+    })?;
+    result
+}
+
+fn set_pause_checkmark(app: &AppHandle, paused: bool) {
+    crate::tray_state::set_checked(app, PAUSE_TRAY_ITEM_ID, paused)
+        .unwrap_or_else(|err| tracing::error!("Failed to update tray menu: {}", err));
+}
+
+// Apply the persisted pause before anything registers, so a paused launch binds nothing
+pub fn restore_paused(app: &AppHandle, paused: bool) {
+    app.state::<ShortcutRegistry>()
+        .paused
+        .store(paused, Ordering::SeqCst);
+    set_pause_checkmark(app, paused);
+}
+
+// Pause or resume every global shortcut at once, persist it and update the tray checkmark
+pub fn set_paused(app: &AppHandle, paused: bool) -> Result<(), String> {
+    let was_paused = app
+        .state::<ShortcutRegistry>()
+        .paused
+        .swap(paused, Ordering::SeqCst);
+    if was_paused != paused {
+        if !paused {
+            refresh(app);
+        } else if !capturing(app) && !suspended(app) {
+            release_bound(app);
+        }
+    }
+    set_pause_checkmark(app, paused);
+    settings::update(app, |settings| settings.hotkeys_paused = paused)
+}
+
+// Whether every global shortcut is paused
+pub fn is_paused(app: &AppHandle) -> bool {
+    paused(app)
+}
+
+// Release every bound accelerator once Aura's pause flag is set; the error lists the
+// accelerators that stayed bound. refresh binds them again on resume
+pub fn suspend(app: &AppHandle) -> Result<(), String> {
+    // Capture mode and the hotkey pause have already released everything
+    if capturing(app) || paused(app) {
+        return Ok(());
+    }
+    let failed = release_bound(app);
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(failed.join(", "))
+    }
+}
+
+fn registrations(app: &AppHandle, failed_only: bool) -> Vec<ShortcutRegistration> {
+    let registry = app.state::<ShortcutRegistry>();
+    let entries = registry.entries.lock().unwrap();
+    entries
+        .values()
+        .filter(|entry| entry.enabled && (entry.active || entry.error.is_some()))
+        .filter(|entry| !failed_only || entry.error.is_some())
+        .map(|entry| ShortcutRegistration {
+            accelerator: entry.accelerator.clone(),
+            registered: entry.active,
+            error: entry.error.clone(),
+        })
+        .collect()
+}
+
+// Emit shortcut-registration-failed with every accelerator that could not be bound
+pub fn notify_failures(app: &AppHandle) {
+    let failures = registrations(app, true);
+    if failures.is_empty() {
+        return;
+    }
+    if let Some(window) = crate::main_window(app) {
+        window
+            .emit("shortcut-registration-failed", failures)
+            .unwrap_or_else(|err| tracing::error!("Failed to emit shortcut failures: {}", err));
+    }
+}
+
+// Registration outcome of every global shortcut, for the settings UI
+#[tauri::command]
+pub fn get_shortcut_status(app: AppHandle) -> ShortcutStatus {
+    let shortcuts = registrations(&app, false);
+    let registered = shortcuts
+        .iter()
+        .filter(|shortcut| shortcut.registered)
+        .map(|shortcut| shortcut.accelerator.clone())
+        .collect();
+    let last_error = if shortcuts.iter().any(|shortcut| shortcut.error.is_some()) {
+        app.state::<ShortcutRegistry>()
+            .last_error
+            .lock()
+            .unwrap()
+            .clone()
+    } else {
+        None
+    };
+    ShortcutStatus {
+        registered,
+        shortcuts,
+        last_error,
+    }
+}
+
+// Re-run every registration, e.g. from the "Repair Shortcuts" tray item, and report the result
+#[tauri::command]
+pub fn reregister_shortcuts(app: AppHandle) -> Vec<ShortcutRegistration> {
+    refresh(&app);
+    registrations(&app, false)
+}
+
+// Every accelerator Aura holds or tried to hold, so clashes are visible at a glance
+#[tauri::command]
+pub fn list_registered_shortcuts(app: AppHandle) -> Vec<RegisteredShortcut> {
+    let paused = paused(&app) || suspended(&app);
+    let registry = app.state::<ShortcutRegistry>();
+    let entries = registry.entries.lock().unwrap();
+    entries
+        .iter()
+        .map(|(id, entry)| RegisteredShortcut {
+            id: id.clone(),
+            accelerator: entry.accelerator.clone(),
+            active: entry.active && entry.enabled && !paused,
+            enabled: entry.enabled,
+            description: entry.description.clone(),
+        })
+        .collect()
+}
+
+// Suspend all global shortcuts while the settings UI records a new binding
+#[tauri::command]
+pub fn begin_shortcut_capture(app: AppHandle) {
+    begin_capture(&app);
+}
+
+// Restore the global shortcuts suspended by begin_shortcut_capture
+#[tauri::command]
+pub fn end_shortcut_capture(app: AppHandle) {
+    end_capture(&app);
+}
+
+// Turn a single global shortcut off or back on, by its registry id
+#[tauri::command]
+pub fn set_shortcut_enabled(app: AppHandle, id: String, enabled: bool) -> Result<(), String> {
+    set_enabled(&app, &id, enabled)
+}
+
+// Pause or resume all global shortcuts, e.g. while playing a fullscreen game
+#[tauri::command]
+pub fn set_all_shortcuts_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    set_paused(&app, !enabled)
+}
@@ -0,0 +1,152 @@
+use std::process::{self, Command};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use tauri::{AppHandle, Manager, State};
+
+use crate::{file_watch, push_to_talk, recording, settings, shortcut_registry};
+
+// How long the frontend gets to acknowledge app-closing before the app exits anyway
+const CLOSING_ACK_TIMEOUT: Duration = Duration::from_secs(2);
+
+// A restarted instance is launched with this argument and the pid of the one it replaces
+const RELAUNCH_ARG: &str = "--relaunch-after";
+
+// Longest a restarted instance waits for the old one to exit, and how often it looks
+const RELAUNCH_WAIT: Duration = Duration::from_secs(10);
+const RELAUNCH_POLL: Duration = Duration::from_millis(100);
+
+#[derive(Default)]
+struct ClosingState {
+    closing: bool,
+    acknowledged: bool,
+    cleaned_up: bool,
+}
+
+// Progress of a quit, shared between the quit path, the frontend and the exit event
+#[derive(Default)]
+pub struct Closing {
+    state: Mutex<ClosingState>,
+    acknowledged: Condvar,
+}
+
+// Finish any recording so its WAV file is closed, release the microphone hook, global
+// shortcuts and file watchers and flush settings, once
+pub fn cleanup(app: &AppHandle) {
+    {
+        let closing = app.state::<Closing>();
+        let mut state = closing.state.lock().unwrap();
+        if state.cleaned_up {
+            return;
+        }
+        state.cleaned_up = true;
+    }
+
+    recording::stop(app).unwrap_or_else(|err| tracing::error!("Failed to stop recording: {}", err));
+    push_to_talk::shutdown(app);
+    shortcut_registry::unregister_all(app);
+    file_watch::stop_all(app);
+    settings::save(app, &settings::current(app))
+        .unwrap_or_else(|err| tracing::error!("Failed to save settings: {}", err));
+}
+
+// Clean up, give the frontend a moment to finish via app-closing, then exit the event loop
+pub fn quit(app: &AppHandle) {
+    {
+        let closing = app.state::<Closing>();
+        let mut state = closing.state.lock().unwrap();
+        if state.closing {
+            return;
+        }
+        state.closing = true;
+    }
+
+    cleanup(app);
+
+    let window = match crate::main_window(app) {
+        Some(window) => window,
+        None => return app.exit(0),
+    };
+    if let Err(err) = window.emit("app-closing", ()) {
+        tracing::error!("Failed to emit app-closing: {}", err);
+        return app.exit(0);
+    }
+
+    // Waiting on the event loop thread would stall the acknowledgement itself
+    let app = app.clone();
+    thread::spawn(move || {
+        let closing = app.state::<Closing>();
+        let state = closing.state.lock().unwrap();
+        let (_state, wait) = closing
+            .acknowledged
+            .wait_timeout_while(state, CLOSING_ACK_TIMEOUT, |state| !state.acknowledged)
+            .unwrap();
+        if wait.timed_out() {
+            tracing::warn!("Frontend did not acknowledge app-closing, exiting anyway");
+        }
+        app.exit(0);
+    });
+}
+
+// Run before the builder. An instance launched by restart waits for the one it replaces to
+// exit; otherwise the single-instance lock would still be held and the launch would be handed
+// to the old process on its way out, leaving nothing running
+pub fn wait_for_previous_instance() {
+    let mut args = std::env::args().skip(1);
+    let pid = match (args.next(), args.next()) {
+        (Some(arg), Some(pid)) if arg == RELAUNCH_ARG => pid.parse::<usize>().ok(),
+        _ => None,
+    };
+    let pid = match pid {
+        Some(pid) => Pid::from(pid),
+        None => return,
+    };
+
+    let started = Instant::now();
+    let mut system = System::new();
+    while system.refresh_process_specifics(pid, ProcessRefreshKind::new()) {
+        if started.elapsed() >= RELAUNCH_WAIT {
+            tracing::warn!(
+                "Previous instance {} is still running, starting anyway",
+                pid
+            );
+            return;
+        }
+        thread::sleep(RELAUNCH_POLL);
+    }
+}
+
+// Clean up as for a quit and start a fresh instance, e.g. after an update or a setting that
+// only applies at launch. The new instance waits for this one to exit before it starts
+pub fn restart(app: &AppHandle) {
+    tracing::info!("Restarting");
+    cleanup(app);
+    let relaunch = tauri::api::process::current_binary(&app.env()).and_then(|binary| {
+        Command::new(binary)
+            .arg(RELAUNCH_ARG)
+            .arg(process::id().to_string())
+            .spawn()
+    });
+    match relaunch {
+        Ok(_) => app.exit(0),
+        Err(err) => {
+            tracing::error!("Failed to relaunch, restarting in place: {}", err);
+            app.restart();
+        }
+    }
+}
+
+// Restart Aura; the frontend confirms with the user first
+#[tauri::command]
+pub fn restart_app(app: AppHandle) {
+    restart(&app);
+}
+
+// Called by the frontend once it has finished its own work after app-closing
+#[tauri::command]
+pub fn acknowledge_closing(closing: State<Closing>) {
+    closing.state.lock().unwrap().acknowledged = true;
+    closing.acknowledged.notify_all();
+}
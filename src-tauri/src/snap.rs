@@ -0,0 +1,127 @@
+use tauri::{AppHandle, Manager, PhysicalPosition, Window};
+
+use crate::monitor;
+use crate::settings;
+use crate::shortcut_registry;
+
+// Optional accelerators that snap the main window, clockwise from the top-left corner
+const SNAP_SHORTCUTS: [(&str, Corner); 4] = [
+    ("CmdOrCtrl+Alt+Left", Corner::TopLeft),
+    ("CmdOrCtrl+Alt+Up", Corner::TopRight),
+    ("CmdOrCtrl+Alt+Right", Corner::BottomRight),
+    ("CmdOrCtrl+Alt+Down", Corner::BottomLeft),
+];
+
+// Corner of the monitor work area
+#[derive(Debug, Clone, Copy)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "top-left" => Ok(Corner::TopLeft),
+            "top-right" => Ok(Corner::TopRight),
+            "bottom-left" => Ok(Corner::BottomLeft),
+            "bottom-right" => Ok(Corner::BottomRight),
+            other => Err(format!(
+                "Unknown corner '{}', expected top-left, top-right, bottom-left or bottom-right",
+                other
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Corner::TopLeft => "top-left",
+            Corner::TopRight => "top-right",
+            Corner::BottomLeft => "bottom-left",
+            Corner::BottomRight => "bottom-right",
+        }
+    }
+}
+
+// Move the window into a corner of the work area of the monitor it is on
+pub fn snap_to_corner(window: &Window, corner: Corner) -> Result<(), String> {
+    let monitor = window
+        .current_monitor()
+        .map_err(|err| err.to_string())?
+        .ok_or("Window is not on any monitor")?;
+    let area = monitor::work_area(&monitor);
+    let size = window.outer_size().map_err(|err| err.to_string())?;
+
+    // The margin is configured in logical pixels
+    let margin_setting = settings::current(&window.app_handle()).snap_margin;
+    let margin = (margin_setting * monitor.scale_factor()).round() as i32;
+
+    let left = area.x + margin;
+    let top = area.y + margin;
+    let right = area.x + area.width as i32 - size.width as i32 - margin;
+    let bottom = area.y + area.height as i32 - size.height as i32 - margin;
+
+    let (x, y) = match corner {
+        Corner::TopLeft => (left, top),
+        Corner::TopRight => (right.max(left), top),
+        Corner::BottomLeft => (left, bottom.max(top)),
+        Corner::BottomRight => (right.max(left), bottom.max(top)),
+    };
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|err| err.to_string())
+}
+
+// Dock the main window into a corner of its current monitor
+#[tauri::command]
+pub fn snap_window_to_corner(app: AppHandle, corner: String) -> Result<(), String> {
+    let corner = Corner::parse(&corner)?;
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    snap_to_corner(&window, corner)
+}
+
+fn shortcut_id(corner: Corner) -> String {
+    format!("snap-{}", corner.name())
+}
+
+// Register the Ctrl+Alt+Arrow shortcuts that snap the main window; failures are kept in the
+// shortcut registry
+pub fn register_shortcuts(app: &AppHandle) {
+    for (accelerator, corner) in SNAP_SHORTCUTS {
+        let app_handle = app.clone();
+        let _ = shortcut_registry::register(
+            app,
+            &shortcut_id(corner),
+            accelerator,
+            &format!("Snap the window to the {} corner", corner.name()),
+            move || {
+                if let Some(window) = crate::main_window(&app_handle) {
+                    if let Err(err) = snap_to_corner(&window, corner) {
+                        tracing::error!("Failed to snap window: {}", err);
+                    }
+                }
+            },
+        );
+    }
+}
+
+// Bind or drop the snap shortcuts and persist the choice
+pub fn apply(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    if enabled {
+        register_shortcuts(app);
+        shortcut_registry::notify_failures(app);
+    } else {
+        for (_, corner) in SNAP_SHORTCUTS {
+            shortcut_registry::unregister(app, &shortcut_id(corner));
+        }
+    }
+    settings::update(app, |settings| settings.snap_shortcuts = enabled)
+}
+
+// Turn the Ctrl+Alt+Arrow snap shortcuts on or off
+#[tauri::command]
+pub fn set_snap_shortcuts(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply(&app, enabled)
+}
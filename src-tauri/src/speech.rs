@@ -0,0 +1,89 @@
+use std::sync::Mutex;
+
+use tauri::State;
+use tts::{Tts, Voice};
+
+struct Speaker {
+    tts: Tts,
+    default_voice: Option<Voice>,
+}
+
+// Text-to-speech engine, created on first use
+#[derive(Default)]
+pub struct Speech(Mutex<Option<Speaker>>);
+
+fn with_speaker<T>(
+    speech: &Speech,
+    action: impl FnOnce(&mut Speaker) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut speaker = speech.0.lock().unwrap();
+    if speaker.is_none() {
+        let tts = Tts::default().map_err(|err| format!("Text-to-speech unavailable: {}", err))?;
+        let default_voice = tts.voice().ok().flatten();
+        *speaker = Some(Speaker { tts, default_voice });
+    }
+    action(speaker.as_mut().expect("speaker was just created"))
+}
+
+fn find_voice(tts: &Tts, name: &str) -> Result<Voice, String> {
+    tts.voices()
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .find(|voice| voice.name() == name || voice.id() == name)
+        .ok_or_else(|| format!("Unknown voice '{}'", name))
+}
+
+// Speak text aloud, cutting off anything still being spoken; rate is relative to normal speed
+#[tauri::command]
+pub fn speak(
+    speech: State<Speech>,
+    text: String,
+    voice: Option<String>,
+    rate: Option<f32>,
+) -> Result<(), String> {
+    with_speaker(&speech, |speaker| {
+        let tts = &mut speaker.tts;
+        let features = tts.supported_features();
+
+        if features.voice {
+            let voice = match voice {
+                Some(name) => Some(find_voice(tts, &name)?),
+                None => speaker.default_voice.clone(),
+            };
+            if let Some(voice) = voice {
+                tts.set_voice(&voice).map_err(|err| err.to_string())?;
+            }
+        }
+        if features.rate {
+            let rate = (tts.normal_rate() * rate.unwrap_or(1.0))
+                .max(tts.min_rate())
+                .min(tts.max_rate());
+            tts.set_rate(rate).map_err(|err| err.to_string())?;
+        }
+
+        tts.speak(text, true).map_err(|err| err.to_string())?;
+        Ok(())
+    })
+}
+
+// Interrupt the current utterance
+#[tauri::command]
+pub fn stop_speaking(speech: State<Speech>) -> Result<(), String> {
+    with_speaker(&speech, |speaker| {
+        speaker.tts.stop().map_err(|err| err.to_string())?;
+        Ok(())
+    })
+}
+
+// Names of the installed voices for the settings voice picker
+#[tauri::command]
+pub fn list_tts_voices(speech: State<Speech>) -> Vec<String> {
+    with_speaker(&speech, |speaker| {
+        speaker.tts.voices().map_err(|err| err.to_string())
+    })
+    .map(|voices| voices.iter().map(Voice::name).collect())
+    .unwrap_or_else(|err| {
+        tracing::error!("Failed to list voices: {}", err);
+        Vec::new()
+    })
+}
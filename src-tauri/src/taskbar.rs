@@ -0,0 +1,62 @@
+use tauri::AppHandle;
+
+use crate::{settings, tray_presence, tray_state};
+
+// Tray menu item that hides Aura from the taskbar, or from the Dock on macOS
+pub const TRAY_ITEM_ID: &str = "skip_taskbar";
+
+// Tray item label; macOS windows have no taskbar entry, so there it is the Dock icon
+#[cfg(target_os = "macos")]
+pub const TRAY_ITEM_LABEL: &str = "Hide Dock Icon";
+#[cfg(not(target_os = "macos"))]
+pub const TRAY_ITEM_LABEL: &str = "Hide from Taskbar";
+
+// Switch between a regular app and an accessory app, which has no Dock icon and no menu bar
+#[cfg(target_os = "macos")]
+fn set_hidden(app: &AppHandle, hidden: bool) -> Result<(), String> {
+    use cocoa::appkit::{NSApp, NSApplication, NSApplicationActivationPolicy};
+
+    app.run_on_main_thread(move || unsafe {
+        let policy = if hidden {
+            NSApplicationActivationPolicy::NSApplicationActivationPolicyAccessory
+        } else {
+            NSApplicationActivationPolicy::NSApplicationActivationPolicyRegular
+        };
+        NSApp().setActivationPolicy_(policy);
+    })
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_hidden(app: &AppHandle, hidden: bool) -> Result<(), String> {
+    let window = crate::main_window(app).ok_or("Main window not found")?;
+    window
+        .set_skip_taskbar(hidden)
+        .map_err(|err| err.to_string())
+}
+
+// Without a tray the taskbar entry or Dock icon is the only way back to a hidden window, so it
+// stays whatever the setting says
+fn show_or_hide(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    set_hidden(app, enabled && tray_presence::is_available(app))
+}
+
+// Apply the saved preference once the tray is known to exist or not
+pub fn restore(app: &AppHandle, enabled: bool) {
+    show_or_hide(app, enabled)
+        .unwrap_or_else(|err| tracing::error!("Failed to update taskbar entry: {}", err));
+}
+
+// Hide or show the taskbar entry, persist it and update the tray checkmark
+pub fn apply(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    show_or_hide(app, enabled)?;
+    settings::update(app, |settings| settings.skip_taskbar = enabled)?;
+    tray_state::set_checked(app, TRAY_ITEM_ID, enabled)
+}
+
+// Keep Aura out of the taskbar on Windows and Linux, or out of the Dock on macOS, where it then
+// has no menu bar either. Ignored while there is no tray icon
+#[tauri::command]
+pub fn set_skip_taskbar(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply(&app, enabled)
+}
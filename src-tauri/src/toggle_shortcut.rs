@@ -0,0 +1,135 @@
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, GlobalShortcutManager, Manager};
+
+use crate::settings;
+use crate::shortcut_registry;
+use crate::tray_state;
+
+// Default accelerator for toggling the assistant window
+pub const DEFAULT_TOGGLE_SHORTCUT: &str = "CmdOrCtrl+'";
+
+// Accelerator used when the saved one cannot be registered
+pub const FALLBACK_TOGGLE_SHORTCUT: &str = "CmdOrCtrl+Shift+A";
+
+// Reasons a new toggle shortcut was rejected, shown by the settings UI
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum ShortcutError {
+    InvalidSyntax(String),
+    AlreadyTaken(String),
+    Platform(String),
+}
+
+impl fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortcutError::InvalidSyntax(message)
+            | ShortcutError::AlreadyTaken(message)
+            | ShortcutError::Platform(message) => f.write_str(message),
+        }
+    }
+}
+
+// Registry ids of the window toggle and of the fallback bound when the saved one fails
+const TOGGLE_ID: &str = "toggle";
+const FALLBACK_ID: &str = "toggle-fallback";
+
+// When the toggle shortcut last fired, shared by the toggle and its fallback
+#[derive(Default)]
+pub struct ToggleDebounce(Mutex<Option<Instant>>);
+
+// Some keyboards report one press of the shortcut twice; a second trigger within the configured
+// interval would hide the window it just showed
+fn is_bounce(app: &AppHandle) -> bool {
+    let interval = Duration::from_millis(settings::current(app).toggle_debounce_ms);
+    let debounce = app.state::<ToggleDebounce>();
+    let mut last = debounce.0.lock().unwrap();
+    let now = Instant::now();
+    if last.map_or(false, |last| now.duration_since(last) < interval) {
+        return true;
+    }
+    *last = Some(now);
+    false
+}
+
+// Register an accelerator that toggles the main window
+fn register(app: &AppHandle, id: &str, accelerator: &str) -> Result<(), String> {
+    let app_handle = app.clone();
+    shortcut_registry::register(
+        app,
+        id,
+        accelerator,
+        "Show or hide the assistant",
+        move || {
+            if !is_bounce(&app_handle) {
+                crate::toggle_main_window(&app_handle);
+            }
+        },
+    )
+}
+
+// Bind the saved toggle shortcut, falling back to the default alternative if it is unavailable
+pub fn register_saved(app: &AppHandle, accelerator: &str) {
+    // The failure stays in the registry so the user is told to pick another binding
+    if register(app, TOGGLE_ID, accelerator).is_err() && accelerator != FALLBACK_TOGGLE_SHORTCUT {
+        tracing::warn!("Using {} to toggle the window", FALLBACK_TOGGLE_SHORTCUT);
+        let _ = register(app, FALLBACK_ID, FALLBACK_TOGGLE_SHORTCUT);
+    }
+}
+
+// Move the window toggle to a new accelerator and persist it
+pub fn apply(app: &AppHandle, accelerator: String) -> Result<(), ShortcutError> {
+    // Validate the new accelerator before touching the existing binding
+    let already_registered = app
+        .global_shortcut_manager()
+        .is_registered(&accelerator)
+        .map_err(|err| {
+            ShortcutError::InvalidSyntax(format!("Invalid shortcut '{}': {}", accelerator, err))
+        })?;
+    // The fallback only stands in for the toggle, so it may hand its keys over
+    let held_by_fallback =
+        shortcut_registry::accelerator(app, FALLBACK_ID).as_deref() == Some(accelerator.as_str());
+    if held_by_fallback {
+        shortcut_registry::unregister(app, FALLBACK_ID);
+    } else if already_registered {
+        if shortcut_registry::accelerator(app, TOGGLE_ID).as_deref() == Some(accelerator.as_str()) {
+            return Ok(());
+        }
+        return Err(ShortcutError::AlreadyTaken(format!(
+            "Shortcut '{}' is already used by another Aura action",
+            accelerator
+        )));
+    }
+
+    // The syntax is already known to be valid, so a refusal means another app holds the keys;
+    // the registry keeps the previous binding in that case so the window stays reachable
+    if let Err(err) = register(app, TOGGLE_ID, &accelerator) {
+        shortcut_registry::notify_failures(app);
+        return Err(ShortcutError::AlreadyTaken(format!(
+            "Shortcut '{}' is taken by another application: {}",
+            accelerator, err
+        )));
+    }
+    shortcut_registry::unregister(app, FALLBACK_ID);
+    tray_state::refresh_tooltip(app);
+
+    settings::update(app, |settings| settings.toggle_shortcut = accelerator)
+        .map_err(ShortcutError::Platform)
+}
+
+// Accelerator that opens the window right now; the fallback only exists while the chosen one
+// is refused
+pub fn active_accelerator(app: &AppHandle) -> Option<String> {
+    shortcut_registry::accelerator(app, FALLBACK_ID)
+        .or_else(|| shortcut_registry::accelerator(app, TOGGLE_ID))
+}
+
+// Change the accelerator that shows and hides the assistant
+#[tauri::command]
+pub fn set_toggle_shortcut(app: AppHandle, accelerator: String) -> Result<(), ShortcutError> {
+    apply(&app, accelerator)
+}
@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+// Menu id prefix that keeps frontend actions apart from the fixed tray items
+pub const ID_PREFIX: &str = "action:";
+
+// Action the frontend currently offers in the tray, e.g. "Cancel current task"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrayAction {
+    pub id: String,
+    pub label: String,
+    pub enabled: bool,
+}
+
+// Frontend actions currently in the tray menu, in the order given
+#[derive(Default)]
+pub struct TrayActions(Mutex<Vec<TrayAction>>);
+
+// Sent when a frontend action is clicked
+#[derive(Debug, Clone, Serialize)]
+pub struct TrayActionClicked {
+    pub id: String,
+}
+
+// Actions for a menu rebuild
+pub fn current(app: &AppHandle) -> Vec<TrayAction> {
+    app.state::<TrayActions>().0.lock().unwrap().clone()
+}
+
+// Emit tray-action for a clicked frontend action; other menu ids are ignored
+pub fn on_menu_click(app: &AppHandle, menu_id: &str) {
+    let id = match menu_id.strip_prefix(ID_PREFIX) {
+        Some(id) => id.to_string(),
+        None => return,
+    };
+    app.emit_all("tray-action", TrayActionClicked { id })
+        .unwrap_or_else(|err| tracing::error!("Failed to emit tray action: {}", err));
+}
+
+// Replace the frontend actions in the tray menu; an empty list removes the section. The fixed
+// items stay in place and the menu is only rebuilt when the actions changed
+#[tauri::command]
+pub fn set_tray_actions(app: AppHandle, actions: Vec<TrayAction>) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = actions.iter().find(|action| !seen.insert(&action.id)) {
+        return Err(format!("Duplicate tray action '{}'", duplicate.id));
+    }
+    {
+        let state = app.state::<TrayActions>();
+        let mut current = state.0.lock().unwrap();
+        if *current == actions {
+            return Ok(());
+        }
+        *current = actions;
+    }
+    crate::rebuild_tray_menu(&app)
+}
@@ -0,0 +1,110 @@
+use tauri::Icon;
+
+// Bubble color, a red that reads on light and dark taskbars
+const BUBBLE: [u8; 3] = [0xE5, 0x39, 0x35];
+
+// 3x5 glyphs for the badge text, one row per entry, most significant bit on the left
+fn glyph(ch: char) -> [u8; 5] {
+    match ch {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        _ => [0; 5],
+    }
+}
+
+// Blend a color over one pixel with the given coverage
+fn blend(rgba: &mut [u8], index: usize, color: [u8; 3], coverage: f32) {
+    let pixel = &mut rgba[index..index + 4];
+    for channel in 0..3 {
+        let below = pixel[channel] as f32;
+        pixel[channel] = (below + (color[channel] as f32 - below) * coverage).round() as u8;
+    }
+    let alpha = pixel[3] as f32 / 255.0;
+    pixel[3] = ((alpha + (1.0 - alpha) * coverage) * 255.0).round() as u8;
+}
+
+// Draw a count bubble in the bottom-right corner of an RGBA icon. Icons Tauri still has to
+// decode are returned unchanged
+pub fn composite(base: &Icon, label: &str) -> Icon {
+    let (mut rgba, width, height) = match base {
+        Icon::Rgba {
+            rgba,
+            width,
+            height,
+        } => (rgba.clone(), *width as usize, *height as usize),
+        other => return other.clone(),
+    };
+
+    // Glyphs are scaled to stay legible at the 32px tray size and shrink with smaller icons
+    let scale = (height / 16).max(1);
+    let glyph_width = 3 * scale;
+    let spacing = scale;
+    let chars = label.chars().count();
+    let text_width = chars * glyph_width + chars.saturating_sub(1) * spacing;
+    let text_height = 5 * scale;
+
+    let bubble_height = (text_height + 2 * scale).min(height);
+    let bubble_width = (text_width + 2 * scale).max(bubble_height).min(width);
+    let left = width - bubble_width;
+    let top = height - bubble_height;
+    let radius = bubble_height as f32 / 2.0;
+
+    // Pill shape, supersampled 4x4 for smooth edges
+    for y in top..height {
+        for x in left..width {
+            let mut inside = 0;
+            for sub_y in 0..4 {
+                for sub_x in 0..4 {
+                    let px = (x - left) as f32 + (sub_x as f32 + 0.5) / 4.0;
+                    let py = (y - top) as f32 + (sub_y as f32 + 0.5) / 4.0;
+                    let cx = px.clamp(radius, bubble_width as f32 - radius);
+                    let (dx, dy) = (px - cx, py - radius);
+                    if dx * dx + dy * dy <= radius * radius {
+                        inside += 1;
+                    }
+                }
+            }
+            if inside > 0 {
+                blend(&mut rgba, (y * width + x) * 4, BUBBLE, inside as f32 / 16.0);
+            }
+        }
+    }
+
+    // White text centered in the bubble
+    let text_left = left + (bubble_width - text_width) / 2;
+    let text_top = top + (bubble_height - text_height) / 2;
+    for (position, ch) in label.chars().enumerate() {
+        let glyph_left = text_left + position * (glyph_width + spacing);
+        for (row, bits) in glyph(ch).iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+                for y in 0..scale {
+                    for x in 0..scale {
+                        let px = glyph_left + column * scale + x;
+                        let py = text_top + row * scale + y;
+                        if px < width && py < height {
+                            blend(&mut rgba, (py * width + px) * 4, [0xFF; 3], 1.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Icon::Rgba {
+        rgba,
+        width: width as u32,
+        height: height as u32,
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, SystemTray};
+
+use crate::{settings, settings_window};
+
+// What a left click on the tray icon does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrayLeftClickAction {
+    ToggleWindow,
+    ShowMenu,
+    None,
+}
+
+// Whether this platform can carry out the action. Windows only opens the tray menu on right
+// click and Tauri cannot open it from code, so show-menu is refused there
+pub fn is_supported(action: TrayLeftClickAction) -> bool {
+    !(cfg!(target_os = "windows") && action == TrayLeftClickAction::ShowMenu)
+}
+
+// Open the menu on left click for show-menu. Only macOS lets Tauri choose, and only when the
+// tray is created, so a change applies from the next launch; Linux always opens the menu
+pub fn configure_tray(
+    tray: SystemTray,
+    #[cfg_attr(not(target_os = "macos"), allow(unused_variables))] action: TrayLeftClickAction,
+) -> SystemTray {
+    #[cfg(target_os = "macos")]
+    let tray = tray.with_menu_on_left_click(action == TrayLeftClickAction::ShowMenu);
+    tray
+}
+
+// Clicks on the tray icon waiting to be told apart from a double click. The generation tells
+// a delayed left click whether a later click superseded it
+#[derive(Default)]
+pub struct TrayClicks(Mutex<ClickState>);
+
+#[derive(Default)]
+struct ClickState {
+    generation: u64,
+    // Windows follows a double click with the left click of its second release
+    swallow_next: bool,
+}
+
+// How long a left click waits for a second one. Only Windows delivers double clicks, and it
+// reports their first click as a left click too
+#[cfg(target_os = "windows")]
+fn double_click_delay() -> Option<Duration> {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetDoubleClickTime;
+
+    Some(Duration::from_millis(unsafe { GetDoubleClickTime() } as u64))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn double_click_delay() -> Option<Duration> {
+    None
+}
+
+// Run the configured left-click action, once it is clear the click is not half of a double
+// click
+pub fn on_left_click(app: &AppHandle) {
+    if settings::current(app).tray_left_click_action != TrayLeftClickAction::ToggleWindow {
+        return;
+    }
+    let delay = match double_click_delay() {
+        Some(delay) => delay,
+        None => return crate::toggle_main_window(app),
+    };
+
+    let generation = {
+        let clicks = app.state::<TrayClicks>();
+        let mut clicks = clicks.0.lock().unwrap();
+        if clicks.swallow_next {
+            clicks.swallow_next = false;
+            return;
+        }
+        clicks.generation += 1;
+        clicks.generation
+    };
+    let app = app.clone();
+    thread::spawn(move || {
+        thread::sleep(delay);
+        let current = app.state::<TrayClicks>().0.lock().unwrap().generation == generation;
+        if current {
+            crate::toggle_main_window(&app);
+        }
+    });
+}
+
+// A double click opens the settings window and cancels the pending left click
+pub fn on_double_click(app: &AppHandle) {
+    {
+        let clicks = app.state::<TrayClicks>();
+        let mut clicks = clicks.0.lock().unwrap();
+        clicks.generation += 1;
+        clicks.swallow_next = true;
+    }
+    if let Err(err) = settings_window::open(app) {
+        tracing::error!("Failed to open settings: {}", err);
+    }
+}
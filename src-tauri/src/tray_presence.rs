@@ -0,0 +1,89 @@
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State, SystemTray};
+
+// Why the tray icon could not be shown; None while it is up
+#[derive(Default)]
+pub struct TrayPresence(Mutex<Option<String>>);
+
+// Tray availability for the settings screen, also sent as tray-unavailable
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayStatusReport {
+    pub available: bool,
+    pub reason: Option<String>,
+}
+
+// GNOME without the AppIndicator extension and some Wayland sessions accept the icon but have no
+// StatusNotifier host to draw it. Without dbus-send there is no telling, so the tray is assumed
+#[cfg(target_os = "linux")]
+fn missing_host() -> Option<String> {
+    let output = std::process::Command::new("dbus-send")
+        .args([
+            "--session",
+            "--print-reply",
+            "--dest=org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus.NameHasOwner",
+            "string:org.kde.StatusNotifierWatcher",
+        ])
+        .output()
+        .ok()?;
+    let reply = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() && reply.contains("boolean false") {
+        Some("The desktop has no system tray (StatusNotifier host)".to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn missing_host() -> Option<String> {
+    None
+}
+
+// Create the tray icon, or leave Aura as a window-only app when the desktop has no tray
+pub fn create(app: &AppHandle, tray: SystemTray) {
+    let result = match missing_host() {
+        Some(reason) => Err(reason),
+        None => tray.build(app).map(|_| ()).map_err(|err| err.to_string()),
+    };
+    let reason = match result {
+        Ok(()) => return,
+        Err(reason) => reason,
+    };
+
+    tracing::warn!("System tray unavailable: {}", reason);
+    *app.state::<TrayPresence>().0.lock().unwrap() = Some(reason.clone());
+
+    // A hidden window would be unreachable, so it gets a taskbar entry instead of the tray
+    if let Some(window) = crate::main_window(app) {
+        window
+            .set_skip_taskbar(false)
+            .unwrap_or_else(|err| tracing::error!("Failed to show taskbar entry: {}", err));
+    }
+    app.emit_all(
+        "tray-unavailable",
+        TrayStatusReport {
+            available: false,
+            reason: Some(reason),
+        },
+    )
+    .unwrap_or_else(|err| tracing::error!("Failed to emit tray-unavailable: {}", err));
+}
+
+// Whether the tray icon is there to bring a hidden window back
+pub fn is_available(app: &AppHandle) -> bool {
+    app.state::<TrayPresence>().0.lock().unwrap().is_none()
+}
+
+// Report whether the tray icon could be shown, and why not
+#[tauri::command]
+pub fn get_tray_status(presence: State<'_, TrayPresence>) -> TrayStatusReport {
+    let reason = presence.0.lock().unwrap().clone();
+    TrayStatusReport {
+        available: reason.is_none(),
+        reason,
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+// Menu id prefix that keeps recent entries apart from the fixed tray items
+pub const ID_PREFIX: &str = "recent:";
+
+// Most recent documents and conversations shown in the tray
+const MAX_RECENT_ITEMS: usize = 5;
+
+// Recent document or conversation offered in the tray menu
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrayItem {
+    pub id: String,
+    pub label: String,
+}
+
+// Recent entries currently in the tray menu
+#[derive(Default)]
+pub struct RecentTrayItems(Mutex<Vec<TrayItem>>);
+
+// Sent when a recent entry is clicked
+#[derive(Debug, Clone, Serialize)]
+pub struct TrayItemSelected {
+    pub id: String,
+}
+
+// Recent entries for a menu rebuild
+pub fn current(app: &AppHandle) -> Vec<TrayItem> {
+    app.state::<RecentTrayItems>().0.lock().unwrap().clone()
+}
+
+// Emit tray-item-selected for a clicked recent entry; other menu ids are ignored
+pub fn on_menu_click(app: &AppHandle, menu_id: &str) {
+    let id = match menu_id.strip_prefix(ID_PREFIX) {
+        Some(id) => id.to_string(),
+        None => return,
+    };
+    if let Some(window) = crate::main_window(app) {
+        if let Err(err) = crate::show_and_focus(&window) {
+            tracing::error!("Failed to show window: {}", err);
+        }
+    }
+    app.emit_all("tray-item-selected", TrayItemSelected { id })
+        .unwrap_or_else(|err| tracing::error!("Failed to emit tray selection: {}", err));
+}
+
+// Replace the recent entries in the tray menu. The menu is only rebuilt when they changed, so
+// calling this after every task costs nothing
+#[tauri::command]
+pub fn update_tray_menu(app: AppHandle, mut items: Vec<TrayItem>) -> Result<(), String> {
+    items.truncate(MAX_RECENT_ITEMS);
+    {
+        let recent = app.state::<RecentTrayItems>();
+        let mut current = recent.0.lock().unwrap();
+        if *current == items {
+            return Ok(());
+        }
+        *current = items;
+    }
+    crate::rebuild_tray_menu(&app)
+}
@@ -0,0 +1,473 @@
+#[cfg(target_os = "windows")]
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tauri::{AppHandle, Icon, Manager, SystemTrayHandle};
+
+use crate::toggle_shortcut;
+#[cfg(target_os = "windows")]
+use crate::tray_badge;
+
+// Id given to Aura's tray icon so it can be looked up without panicking when it is missing
+pub const TRAY_ID: &str = "aura";
+
+// Menu item at the top of the tray menu showing the assistant state
+pub const STATUS_ITEM_ID: &str = "status";
+
+// Tooltip shown while idle with no custom text when no toggle shortcut is bound
+const DEFAULT_TOOLTIP: &str = "Aura Assistant";
+
+// Windows cuts tray tooltips at 127 UTF-16 units
+#[cfg(target_os = "windows")]
+const MAX_TOOLTIP_LEN: Option<usize> = Some(127);
+#[cfg(not(target_os = "windows"))]
+const MAX_TOOLTIP_LEN: Option<usize> = None;
+
+// Changes arriving within this window are folded into one tray update
+const COALESCE_DELAY: Duration = Duration::from_millis(150);
+
+// What the assistant is doing, shown by the tray icon, tooltip and status line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrayState {
+    Idle,
+    Listening,
+    Processing,
+    Error,
+}
+
+impl TrayState {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "idle" => Some(TrayState::Idle),
+            "listening" => Some(TrayState::Listening),
+            "processing" | "thinking" => Some(TrayState::Processing),
+            "error" => Some(TrayState::Error),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            TrayState::Idle => "Idle",
+            TrayState::Listening => "Listening",
+            TrayState::Processing => "Processing",
+            TrayState::Error => "Error",
+        }
+    }
+
+    // macOS draws template icons in the menu bar's own color, so it gets monochrome variants
+    fn icon(self) -> &'static [u8] {
+        #[cfg(target_os = "macos")]
+        let icon: &[u8] = match self {
+            TrayState::Idle => include_bytes!("../icons/tray-idle-template.png"),
+            TrayState::Listening => include_bytes!("../icons/tray-listening-template.png"),
+            TrayState::Processing => include_bytes!("../icons/tray-processing-template.png"),
+            TrayState::Error => include_bytes!("../icons/tray-error-template.png"),
+        };
+        #[cfg(not(target_os = "macos"))]
+        let icon: &[u8] = match self {
+            TrayState::Idle => include_bytes!("../icons/tray-idle.png"),
+            TrayState::Listening => include_bytes!("../icons/tray-listening.png"),
+            TrayState::Processing => include_bytes!("../icons/tray-processing.png"),
+            TrayState::Error => include_bytes!("../icons/tray-error.png"),
+        };
+        icon
+    }
+}
+
+// Dimmed icon shown while Aura is paused
+fn paused_icon() -> &'static [u8] {
+    #[cfg(target_os = "macos")]
+    let icon: &[u8] = include_bytes!("../icons/tray-paused-template.png");
+    #[cfg(not(target_os = "macos"))]
+    let icon: &[u8] = include_bytes!("../icons/tray-paused.png");
+    icon
+}
+
+#[derive(Clone)]
+struct TrayStatusState {
+    requested: TrayState,
+    shown: TrayState,
+    flush_pending: bool,
+    // Text from set_tray_tooltip or task progress, shown instead of the state
+    tooltip: Option<String>,
+    // Aura is paused; the state keeps updating underneath the dimmed icon
+    paused: bool,
+    // Background tasks finished since the user last looked, 0 for no badge
+    badge: u32,
+}
+
+// Tray icons decoded once; set_icon would decode Icon::Raw again on every change
+struct TrayIcons {
+    idle: Icon,
+    listening: Icon,
+    processing: Icon,
+    error: Icon,
+    paused: Icon,
+}
+
+impl TrayIcons {
+    fn load() -> Self {
+        TrayIcons {
+            idle: decode(TrayState::Idle.icon()),
+            listening: decode(TrayState::Listening.icon()),
+            processing: decode(TrayState::Processing.icon()),
+            error: decode(TrayState::Error.icon()),
+            paused: decode(paused_icon()),
+        }
+    }
+
+    fn get(&self, status: &TrayStatusState) -> Icon {
+        let icon = match status.shown {
+            _ if status.paused => &self.paused,
+            TrayState::Idle => &self.idle,
+            TrayState::Listening => &self.listening,
+            TrayState::Processing => &self.processing,
+            TrayState::Error => &self.error,
+        };
+        icon.clone()
+    }
+}
+
+// Decode a bundled PNG to RGBA, leaving it to Tauri if it is not plain 8-bit RGBA
+fn decode(png_bytes: &'static [u8]) -> Icon {
+    let decoded = png::Decoder::new(png_bytes)
+        .read_info()
+        .and_then(|mut reader| {
+            let mut rgba = vec![0; reader.output_buffer_size()];
+            let frame = reader.next_frame(&mut rgba)?;
+            rgba.truncate(frame.buffer_size());
+            Ok((rgba, frame))
+        });
+    match decoded {
+        Ok((rgba, frame))
+            if frame.color_type == png::ColorType::Rgba
+                && frame.bit_depth == png::BitDepth::Eight =>
+        {
+            Icon::Rgba {
+                rgba,
+                width: frame.width,
+                height: frame.height,
+            }
+        }
+        _ => Icon::Raw(png_bytes.to_vec()),
+    }
+}
+
+// Latest requested state and the one the tray currently shows
+pub struct TrayStatus {
+    state: Mutex<TrayStatusState>,
+    icons: TrayIcons,
+    // Icons with a count bubble, by paused flag, state and badge text
+    #[cfg(target_os = "windows")]
+    badged: Mutex<HashMap<(bool, TrayState, String), Icon>>,
+}
+
+impl Default for TrayStatus {
+    fn default() -> Self {
+        TrayStatus {
+            state: Mutex::new(TrayStatusState {
+                requested: TrayState::Idle,
+                shown: TrayState::Idle,
+                flush_pending: false,
+                tooltip: None,
+                paused: false,
+                badge: 0,
+            }),
+            icons: TrayIcons::load(),
+            #[cfg(target_os = "windows")]
+            badged: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+pub fn status_line(state: TrayState) -> String {
+    format!("Aura: {}", state.label())
+}
+
+fn status_text(status: &TrayStatusState) -> String {
+    if status.paused {
+        "Aura: Paused".to_string()
+    } else {
+        status_line(status.shown)
+    }
+}
+
+// Status line text for the tray menu and tooltip
+pub fn status_title(app: &AppHandle) -> String {
+    status_text(&app.state::<TrayStatus>().state.lock().unwrap())
+}
+
+// Aura's tray icon; some Linux desktops have no tray to put it in
+pub fn tray(app: &AppHandle) -> Result<SystemTrayHandle, String> {
+    app.tray_handle_by_id(TRAY_ID)
+        .ok_or_else(|| "System tray is not available on this desktop".to_string())
+}
+
+// Tick or untick a toggle item in the tray menu; without a tray there is nothing to update
+pub fn set_checked(app: &AppHandle, id: &str, selected: bool) -> Result<(), String> {
+    match app.tray_handle_by_id(TRAY_ID) {
+        Some(tray) => tray
+            .get_item(id)
+            .set_selected(selected)
+            .map_err(|err| err.to_string()),
+        None => Ok(()),
+    }
+}
+
+// Accelerator as the user reads it, e.g. "Ctrl+'" for "CmdOrCtrl+'" on Windows
+fn display_accelerator(accelerator: &str) -> String {
+    let cmd_or_ctrl = if cfg!(target_os = "macos") {
+        "Cmd"
+    } else {
+        "Ctrl"
+    };
+    accelerator
+        .split('+')
+        .map(|part| match part.to_uppercase().as_str() {
+            "CMDORCTRL" | "COMMANDORCONTROL" => cmd_or_ctrl,
+            _ => part,
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+// Tooltip while idle, naming the shortcut that opens the window
+fn idle_tooltip(app: &AppHandle) -> String {
+    match toggle_shortcut::active_accelerator(app) {
+        Some(accelerator) => format!("Aura — press {} to open", display_accelerator(&accelerator)),
+        None => DEFAULT_TOOLTIP.to_string(),
+    }
+}
+
+fn tooltip_text(app: &AppHandle, status: &TrayStatusState) -> String {
+    match &status.tooltip {
+        Some(tooltip) => tooltip.clone(),
+        None if status.shown == TrayState::Idle && !status.paused => idle_tooltip(app),
+        None => status_text(status),
+    }
+}
+
+// Cut text to the platform tooltip limit, marking the cut with an ellipsis
+fn truncate_tooltip(text: &str) -> String {
+    let max = match MAX_TOOLTIP_LEN {
+        Some(max) if text.encode_utf16().count() > max => max,
+        _ => return text.to_string(),
+    };
+    let mut truncated = String::new();
+    let mut len = 0;
+    for ch in text.chars() {
+        len += ch.len_utf16();
+        if len > max - 1 {
+            break;
+        }
+        truncated.push(ch);
+    }
+    truncated.push('…');
+    truncated
+}
+
+// Badge text for a count, capped so it fits the bubble
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn badge_label(count: u32) -> Option<String> {
+    match count {
+        0 => None,
+        1..=9 => Some(count.to_string()),
+        _ => Some("9+".to_string()),
+    }
+}
+
+// Icon for the status; on Windows the badge is drawn onto it, once per state and count
+fn icon(app: &AppHandle, status: &TrayStatusState) -> Icon {
+    let tray_status = app.state::<TrayStatus>();
+    let base = tray_status.icons.get(status);
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(label) = badge_label(status.badge) {
+            let mut badged = tray_status.badged.lock().unwrap();
+            return badged
+                .entry((status.paused, status.shown, label))
+                .or_insert_with_key(|(_, _, label)| tray_badge::composite(&base, label))
+                .clone();
+        }
+    }
+    base
+}
+
+fn show(app: &AppHandle, status: &TrayStatusState) -> Result<(), String> {
+    let tray = tray(app)?;
+    let icon = icon(app, status);
+    let result = (|| {
+        tray.set_icon(icon)?;
+        #[cfg(target_os = "macos")]
+        tray.set_icon_as_template(true)?;
+        // The menu bar has room for text next to the icon, so macOS shows the count there
+        #[cfg(target_os = "macos")]
+        tray.set_title(&badge_label(status.badge).unwrap_or_default())?;
+        tray.set_tooltip(&tooltip_text(app, status))?;
+        tray.get_item(STATUS_ITEM_ID).set_title(status_text(status))
+    })();
+    result.map_err(|err| err.to_string())
+}
+
+fn show_tooltip(app: &AppHandle, text: &str) -> Result<(), String> {
+    let text = if text.is_empty() {
+        None
+    } else {
+        Some(truncate_tooltip(text))
+    };
+    let snapshot = {
+        let status = app.state::<TrayStatus>();
+        let mut status = status.state.lock().unwrap();
+        status.tooltip = text;
+        status.clone()
+    };
+    tray(app)?
+        .set_tooltip(&tooltip_text(app, &snapshot))
+        .map_err(|err| err.to_string())
+}
+
+// Apply the tooltip again once the toggle shortcut is bound or changes
+pub fn refresh_tooltip(app: &AppHandle) {
+    let snapshot = app.state::<TrayStatus>().state.lock().unwrap().clone();
+    let result = tray(app).and_then(|tray| {
+        tray.set_tooltip(&tooltip_text(app, &snapshot))
+            .map_err(|err| err.to_string())
+    });
+    if let Err(err) = result {
+        tracing::warn!("Failed to update tray tooltip: {}", err);
+    }
+}
+
+// Progress of a long-running task, emitted by the frontend
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskProgress {
+    title: String,
+    percent: Option<f64>,
+    #[serde(default)]
+    done: bool,
+}
+
+// Mirror task-progress events in the tray tooltip, e.g. "Summarizing report.xlsx — 40%"
+pub fn watch_task_progress(app: &AppHandle) {
+    let app_handle = app.clone();
+    app.listen_global("task-progress", move |event| {
+        let progress = match event
+            .payload()
+            .and_then(|payload| serde_json::from_str::<TaskProgress>(payload).ok())
+        {
+            Some(progress) => progress,
+            None => {
+                tracing::warn!("Ignoring malformed task-progress event");
+                return;
+            }
+        };
+        let text = match (progress.done, progress.percent) {
+            (true, _) => String::new(),
+            (false, Some(percent)) => format!("{} — {:.0}%", progress.title, percent),
+            (false, None) => progress.title,
+        };
+        if let Err(err) = show_tooltip(&app_handle, &text) {
+            tracing::warn!("Failed to show task progress in the tray: {}", err);
+        }
+    });
+}
+
+// Apply the latest requested state once the burst of changes has settled
+fn flush(app: &AppHandle) {
+    let snapshot = {
+        let status = app.state::<TrayStatus>();
+        let mut status = status.state.lock().unwrap();
+        status.flush_pending = false;
+        if status.requested == status.shown {
+            return;
+        }
+        status.shown = status.requested;
+        status.clone()
+    };
+    if let Err(err) = show(app, &snapshot) {
+        tracing::error!("Failed to update tray state: {}", err);
+    }
+}
+
+// Dim the tray icon and show "Paused" in the status line, or bring back the current state
+pub fn set_paused(app: &AppHandle, paused: bool) -> Result<(), String> {
+    let snapshot = {
+        let status = app.state::<TrayStatus>();
+        let mut status = status.state.lock().unwrap();
+        status.paused = paused;
+        status.clone()
+    };
+    show(app, &snapshot)
+}
+
+// Back to the idle icon right away, e.g. at startup while the tray still shows the app icon
+pub fn reset(app: &AppHandle) {
+    let snapshot = {
+        let status = app.state::<TrayStatus>();
+        let mut status = status.state.lock().unwrap();
+        status.requested = TrayState::Idle;
+        status.shown = TrayState::Idle;
+        status.clone()
+    };
+    if let Err(err) = show(app, &snapshot) {
+        tracing::warn!("Failed to reset tray state: {}", err);
+    }
+}
+
+fn request(app: AppHandle, state: &str) -> Result<(), String> {
+    let state = TrayState::parse(state).ok_or_else(|| format!("Unknown tray state '{}'", state))?;
+    {
+        let status = app.state::<TrayStatus>();
+        let mut status = status.state.lock().unwrap();
+        status.requested = state;
+        if status.flush_pending {
+            return Ok(());
+        }
+        status.flush_pending = true;
+    }
+    thread::spawn(move || {
+        thread::sleep(COALESCE_DELAY);
+        flush(&app);
+    });
+    Ok(())
+}
+
+// Show idle, listening, processing (or thinking) or error in the tray icon; the tooltip and
+// status line follow so they never contradict the icon
+#[tauri::command]
+pub fn set_tray_state(app: AppHandle, state: String) -> Result<(), String> {
+    request(app, &state)
+}
+
+// Swap the tray icon for idle, listening, thinking or error; the same as set_tray_state
+#[tauri::command]
+pub fn set_tray_icon(app: AppHandle, state: String) -> Result<(), String> {
+    set_tray_state(app, state)
+}
+
+// Show how many background tasks finished: a bubble on the icon on Windows, text beside it on
+// macOS; 0 clears it. Linux trays have no room for it, so the count is only kept there
+#[tauri::command]
+pub fn set_tray_badge(app: AppHandle, count: u32) -> Result<(), String> {
+    let snapshot = {
+        let status = app.state::<TrayStatus>();
+        let mut status = status.state.lock().unwrap();
+        if status.badge == count {
+            return Ok(());
+        }
+        status.badge = count;
+        status.clone()
+    };
+    show(&app, &snapshot)
+}
+
+// Show live status text when hovering the tray icon; an empty string restores the default
+#[tauri::command]
+pub fn set_tray_tooltip(app: AppHandle, text: String) -> Result<(), String> {
+    show_tooltip(&app, &text)
+}
@@ -0,0 +1,175 @@
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+#[cfg(feature = "updater")]
+pub use feed::PendingUpdate;
+
+// Tray menu item that checks the release feed
+pub const TRAY_ITEM_ID: &str = "check_updates";
+
+// Reported by the update commands until a signing key and release feed are set up
+const NOT_CONFIGURED: &str =
+    "Updater not configured: the app has no update signing key or release feed";
+
+// Sent when the release feed could not be reached or read, e.g. while offline
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCheckFailed {
+    pub error: String,
+}
+
+fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    app.emit_all(event, payload)
+        .unwrap_or_else(|err| tracing::error!("Failed to emit {}: {}", event, err));
+}
+
+// Updates can only be checked and verified once the updater feature is built in and
+// tauri.conf.json turns the updater on with a public key and at least one endpoint
+fn is_configured(app: &AppHandle) -> bool {
+    let updater = &app.config().tauri.updater;
+    cfg!(feature = "updater")
+        && updater.active
+        && !updater.pubkey.is_empty()
+        && updater
+            .endpoints
+            .as_ref()
+            .map_or(false, |endpoints| !endpoints.is_empty())
+}
+
+// Nothing is ever pending without the updater feature
+#[cfg(not(feature = "updater"))]
+#[derive(Default)]
+pub struct PendingUpdate {}
+
+// Query the release feed and report the outcome as update-available, update-not-available or
+// update-check-failed
+pub async fn check(app: AppHandle) {
+    if is_configured(&app) {
+        #[cfg(feature = "updater")]
+        feed::check(&app).await;
+    } else {
+        emit(
+            &app,
+            "update-check-failed",
+            UpdateCheckFailed {
+                error: NOT_CONFIGURED.to_string(),
+            },
+        );
+    }
+}
+
+// Check for a newer release from the tray without blocking the event loop
+pub fn check_in_background(app: &AppHandle) {
+    tauri::async_runtime::spawn(check(app.clone()));
+}
+
+// Check the release feed; the result arrives as an event
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<(), String> {
+    if !is_configured(&app) {
+        return Err(NOT_CONFIGURED.to_string());
+    }
+    check(app).await;
+    Ok(())
+}
+
+// Download and apply the update found by the last check, then restart into it
+#[tauri::command]
+pub async fn install_update(
+    app: AppHandle,
+    pending: State<'_, PendingUpdate>,
+) -> Result<(), String> {
+    if !is_configured(&app) {
+        return Err(NOT_CONFIGURED.to_string());
+    }
+    #[cfg(feature = "updater")]
+    return feed::install(&app, &pending).await;
+    #[cfg(not(feature = "updater"))]
+    {
+        let _ = pending;
+        Ok(())
+    }
+}
+
+// Release feed access, only built with the updater feature
+#[cfg(feature = "updater")]
+mod feed {
+    use std::sync::Mutex;
+
+    use serde::Serialize;
+    use tauri::updater::UpdateResponse;
+    use tauri::{AppHandle, Manager, Wry};
+
+    use super::{emit, UpdateCheckFailed};
+    use crate::shutdown;
+
+    // Newer release found by the last check, waiting for install_update
+    #[derive(Default)]
+    pub struct PendingUpdate(Mutex<Option<UpdateResponse<Wry>>>);
+
+    // Sent when the release feed offers a newer version
+    #[derive(Debug, Clone, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct UpdateAvailable {
+        pub version: String,
+        pub notes: Option<String>,
+    }
+
+    // Sent when the running version is the latest
+    #[derive(Debug, Clone, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct UpdateNotAvailable {
+        pub current_version: String,
+    }
+
+    // Query the feed and emit update-available, update-not-available or update-check-failed
+    pub async fn check(app: &AppHandle) {
+        let current_version = app.package_info().version.to_string();
+        // Events are off so Tauri's own tauri://update-* events and install listener stay out
+        match app.updater().skip_events().check().await {
+            Ok(update) if update.is_update_available() => {
+                let available = UpdateAvailable {
+                    version: update.latest_version().to_string(),
+                    notes: update.body().cloned(),
+                };
+                tracing::info!("Update {} is available", available.version);
+                *app.state::<PendingUpdate>().0.lock().unwrap() = Some(update);
+                emit(app, "update-available", available);
+            }
+            Ok(_) | Err(tauri::updater::Error::UpToDate) => {
+                emit(
+                    app,
+                    "update-not-available",
+                    UpdateNotAvailable { current_version },
+                );
+            }
+            Err(err) => {
+                tracing::warn!("Update check failed: {}", err);
+                emit(
+                    app,
+                    "update-check-failed",
+                    UpdateCheckFailed {
+                        error: err.to_string(),
+                    },
+                );
+            }
+        }
+    }
+
+    // Install the pending update and restart into it
+    pub async fn install(app: &AppHandle, pending: &PendingUpdate) -> Result<(), String> {
+        let update = pending
+            .0
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or("No update available, check for updates first")?;
+        if let Err(err) = update.clone().download_and_install().await {
+            // Keep the update so the user can retry once the problem is fixed
+            *pending.0.lock().unwrap() = Some(update);
+            return Err(format!("Failed to install update: {}", err));
+        }
+        tracing::info!("Update installed");
+        shutdown::restart(app);
+        Ok(())
+    }
+}
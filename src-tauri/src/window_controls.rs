@@ -0,0 +1,168 @@
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, PhysicalPosition, Position, Size, UserAttentionType, Window};
+
+use crate::settings;
+use crate::window_state;
+
+// Snapshot of the main window flags shown by the titlebar buttons
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowStatus {
+    pub visible: bool,
+    pub focused: bool,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    pub always_on_top: bool,
+    pub decorations: bool,
+}
+
+// Last status sent to the frontend, used to skip duplicate events
+#[derive(Default)]
+pub struct LastWindowStatus(Mutex<Option<WindowStatus>>);
+
+fn read_status(window: &Window) -> tauri::Result<WindowStatus> {
+    Ok(WindowStatus {
+        visible: window.is_visible()?,
+        focused: window.is_focused()?,
+        maximized: window.is_maximized()?,
+        fullscreen: window.is_fullscreen()?,
+        // Tauri cannot query the flag back, so the persisted preference is the source of truth
+        always_on_top: settings::current(&window.app_handle()).always_on_top,
+        decorations: settings::current(&window.app_handle()).decorations,
+    })
+}
+
+// Emit window-state-changed when any of the titlebar flags differ from the last event
+pub fn notify_changed(window: &Window) {
+    let status = match read_status(window) {
+        Ok(status) => status,
+        Err(err) => {
+            tracing::error!("Failed to read window state: {}", err);
+            return;
+        }
+    };
+
+    let app = window.app_handle();
+    let last = app.state::<LastWindowStatus>();
+    let mut last = last.0.lock().unwrap();
+    if *last == Some(status) {
+        return;
+    }
+    *last = Some(status);
+
+    window
+        .emit("window-state-changed", status)
+        .unwrap_or_else(|err| tracing::error!("Failed to emit window state: {}", err));
+}
+
+// Maximize the main window, or restore it when already maximized
+#[tauri::command]
+pub fn toggle_maximize(app: AppHandle) -> Result<(), String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    let result = if window.is_maximized().map_err(|err| err.to_string())? {
+        window.unmaximize()
+    } else {
+        window.maximize()
+    };
+    result.map_err(|err| err.to_string())?;
+    notify_changed(&window);
+    Ok(())
+}
+
+// Enter or leave fullscreen; leaving returns to the last persisted geometry
+#[tauri::command]
+pub fn set_fullscreen(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    let was_fullscreen = window.is_fullscreen().map_err(|err| err.to_string())?;
+    window
+        .set_fullscreen(enabled)
+        .map_err(|err| err.to_string())?;
+    if was_fullscreen && !enabled {
+        window_state::restore_geometry(&window);
+    }
+    notify_changed(&window);
+    Ok(())
+}
+
+// Report the titlebar flags of the main window in one call
+#[tauri::command]
+pub fn get_window_state(app: AppHandle) -> Result<WindowStatus, String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    read_status(&window).map_err(|err| err.to_string())
+}
+
+// Show or hide the native titlebar and border. Adding or removing the frame would otherwise
+// resize the window around its content and shift it, so the content size and the position
+// are put back afterwards
+pub fn set_frame(window: &Window, enabled: bool) -> tauri::Result<()> {
+    let position = window.outer_position()?;
+    let size = window.inner_size()?;
+    window.set_decorations(enabled)?;
+    window.set_size(Size::Physical(size))?;
+    window.set_position(Position::Physical(position))
+}
+
+// Show or hide the native frame, persist it and tell the titlebar
+pub fn apply_decorations(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let window = crate::main_window(app).ok_or("Main window not found")?;
+    set_frame(&window, enabled).map_err(|err| err.to_string())?;
+    settings::update(app, |settings| settings.decorations = enabled)?;
+    notify_changed(&window);
+    Ok(())
+}
+
+// Switch between the native frame and the borderless overlay with its own titlebar
+#[tauri::command]
+pub fn set_decorations(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply_decorations(&app, enabled)
+}
+
+// Move the frameless main window with the mouse; the resulting moves are persisted by the
+// geometry saver like any other
+#[tauri::command]
+pub fn start_dragging(app: AppHandle) -> Result<(), String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    // Dragging would pull a maximized window out of its maximized frame
+    if window.is_maximized().map_err(|err| err.to_string())? {
+        return Ok(());
+    }
+    window.start_dragging().map_err(|err| err.to_string())
+}
+
+// Drag handler for the custom titlebar region of the borderless window; with the native frame
+// shown that frame moves the window instead
+#[tauri::command]
+pub fn start_drag(app: AppHandle) -> Result<(), String> {
+    if settings::current(&app).decorations {
+        return Ok(());
+    }
+    start_dragging(app)
+}
+
+// Place the main window at a position in physical pixels
+#[tauri::command]
+pub fn set_position(app: AppHandle, x: i32, y: i32) -> Result<(), String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|err| err.to_string())
+}
+
+// Flash the taskbar button or bounce the dock icon when the window is in the background
+#[tauri::command]
+pub fn request_attention(app: AppHandle, critical: bool) -> Result<(), String> {
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    if window.is_focused().map_err(|err| err.to_string())? {
+        return Ok(());
+    }
+    let attention = if critical {
+        UserAttentionType::Critical
+    } else {
+        UserAttentionType::Informational
+    };
+    window
+        .request_user_attention(Some(attention))
+        .map_err(|err| err.to_string())
+}
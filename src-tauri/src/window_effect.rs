@@ -0,0 +1,200 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Window};
+
+use crate::settings;
+
+// Native material drawn behind the transparent main window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WindowEffect {
+    None,
+    Acrylic,
+    Mica,
+    Sidebar,
+    HudWindow,
+    Popover,
+    Menu,
+    UnderWindowBackground,
+}
+
+// Errors returned to the frontend when an effect cannot be applied
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum EffectError {
+    Unsupported(String),
+    Platform(String),
+}
+
+impl fmt::Display for EffectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EffectError::Unsupported(message) | EffectError::Platform(message) => {
+                f.write_str(message)
+            }
+        }
+    }
+}
+
+impl From<tauri::Error> for EffectError {
+    fn from(err: tauri::Error) -> Self {
+        EffectError::Platform(err.to_string())
+    }
+}
+
+impl From<window_vibrancy::Error> for EffectError {
+    fn from(err: window_vibrancy::Error) -> Self {
+        match err {
+            window_vibrancy::Error::UnsupportedPlatform(message)
+            | window_vibrancy::Error::UnsupportedPlatformVersion(message) => {
+                EffectError::Unsupported(message.to_string())
+            }
+            window_vibrancy::Error::NotMainThread(message) => {
+                EffectError::Platform(message.to_string())
+            }
+        }
+    }
+}
+
+// Effects the running OS can draw; acrylic needs Windows 10 1809 and mica Windows 11
+#[cfg(target_os = "windows")]
+pub fn available() -> Vec<WindowEffect> {
+    let build = windows_version::OsVersion::current().build;
+    let mut effects = vec![WindowEffect::None];
+    if build >= 17763 {
+        effects.push(WindowEffect::Acrylic);
+    }
+    if build >= 22000 {
+        effects.push(WindowEffect::Mica);
+    }
+    effects
+}
+
+// Effects the running OS can draw
+#[cfg(target_os = "macos")]
+pub fn available() -> Vec<WindowEffect> {
+    vec![
+        WindowEffect::None,
+        WindowEffect::Sidebar,
+        WindowEffect::HudWindow,
+        WindowEffect::Popover,
+        WindowEffect::Menu,
+        WindowEffect::UnderWindowBackground,
+    ]
+}
+
+// Effects the running OS can draw
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn available() -> Vec<WindowEffect> {
+    vec![WindowEffect::None]
+}
+
+// Remove any effect drawn behind the window
+#[cfg(target_os = "windows")]
+fn clear(window: &Window) -> Result<(), EffectError> {
+    // Only one effect is active at a time and clearing an inactive one is a no-op
+    for effect in available() {
+        match effect {
+            WindowEffect::Acrylic => window_vibrancy::clear_acrylic(window)?,
+            WindowEffect::Mica => window_vibrancy::clear_mica(window)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// Remove any effect drawn behind the window
+#[cfg(target_os = "macos")]
+fn clear(window: &Window) -> Result<(), EffectError> {
+    use cocoa::appkit::NSWindow;
+    use cocoa::base::{id, BOOL, NO};
+    use cocoa::foundation::NSArray;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    // Vibrancy is an NSVisualEffectView inserted below the webview
+    let ns_window = window.ns_window()? as id;
+    unsafe {
+        let subviews: id = msg_send![ns_window.contentView(), subviews];
+        for index in 0..subviews.count() {
+            let view = subviews.objectAtIndex(index);
+            let is_effect: BOOL = msg_send![view, isKindOfClass: class!(NSVisualEffectView)];
+            if is_effect != NO {
+                let _: () = msg_send![view, removeFromSuperview];
+            }
+        }
+    }
+    Ok(())
+}
+
+// Nothing is ever drawn behind the window here
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn clear(_window: &Window) -> Result<(), EffectError> {
+    Ok(())
+}
+
+// Replace the current window effect, removing it entirely for WindowEffect::None
+pub fn apply(window: &Window, effect: WindowEffect) -> Result<(), EffectError> {
+    if !available().contains(&effect) {
+        return Err(EffectError::Unsupported(format!(
+            "The {:?} effect is not available on this system",
+            effect
+        )));
+    }
+
+    clear(window)?;
+    match effect {
+        WindowEffect::None => {}
+        #[cfg(target_os = "windows")]
+        WindowEffect::Acrylic => window_vibrancy::apply_acrylic(window, None)?,
+        #[cfg(target_os = "windows")]
+        WindowEffect::Mica => window_vibrancy::apply_mica(window, None)?,
+        #[cfg(target_os = "macos")]
+        material => {
+            use window_vibrancy::{NSVisualEffectMaterial, NSVisualEffectState};
+
+            let material = match material {
+                WindowEffect::Sidebar => NSVisualEffectMaterial::Sidebar,
+                WindowEffect::HudWindow => NSVisualEffectMaterial::HudWindow,
+                WindowEffect::Popover => NSVisualEffectMaterial::Popover,
+                WindowEffect::Menu => NSVisualEffectMaterial::Menu,
+                _ => NSVisualEffectMaterial::UnderWindowBackground,
+            };
+            // Stay blurred while another app is focused, which is most of the time
+            window_vibrancy::apply_vibrancy(
+                window,
+                material,
+                Some(NSVisualEffectState::Active),
+                None,
+            )?;
+        }
+        #[cfg(not(target_os = "macos"))]
+        _ => {}
+    }
+    Ok(())
+}
+
+// Apply, persist and announce the material behind the main window
+pub fn set(app: &AppHandle, effect: WindowEffect) -> Result<(), EffectError> {
+    let window = crate::main_window(app)
+        .ok_or_else(|| EffectError::Platform("Main window not found".to_string()))?;
+    apply(&window, effect)?;
+
+    settings::update(app, |settings| settings.window_effect = effect)
+        .map_err(EffectError::Platform)?;
+    // The frontend switches to an opaque background when no effect is drawn
+    app.emit_all("window-effect-changed", effect)?;
+    Ok(())
+}
+
+// Change the material behind the main window and remember it for the next launch
+#[tauri::command]
+pub fn set_window_effect(app: AppHandle, effect: WindowEffect) -> Result<(), EffectError> {
+    set(&app, effect)
+}
+
+// List the effects the settings UI can offer on this OS
+#[tauri::command]
+pub fn get_window_effects() -> Vec<WindowEffect> {
+    available()
+}
@@ -0,0 +1,155 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, LogicalSize, Manager, PhysicalPosition, PhysicalSize, Size, Window};
+
+use crate::monitor;
+use crate::settings;
+
+// Length of the resize animation and the number of intermediate frames
+const ANIMATION_DURATION: Duration = Duration::from_millis(150);
+const ANIMATION_FRAMES: u32 = 10;
+
+// Minimum size of the expanded window, matching tauri.conf.json
+const EXPANDED_MIN_WIDTH: f64 = 400.0;
+const EXPANDED_MIN_HEIGHT: f64 = 300.0;
+
+// Layout of the main window: a small input pill or the full results view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WindowMode {
+    Compact,
+    Expanded,
+}
+
+// Window size in logical pixels
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+// Generation of the running animation, so a newer mode change cancels an older one
+#[derive(Default)]
+pub struct ModeAnimation(AtomicU64);
+
+// Allow the compact pill to shrink below the expanded minimum size
+pub fn apply_min_size(window: &Window, mode: WindowMode) -> tauri::Result<()> {
+    match mode {
+        WindowMode::Compact => window.set_min_size(None::<Size>),
+        WindowMode::Expanded => window.set_min_size(Some(LogicalSize::new(
+            EXPANDED_MIN_WIDTH,
+            EXPANDED_MIN_HEIGHT,
+        ))),
+    }
+}
+
+// Compute the final geometry, keeping the window inside the monitor work area
+fn target_geometry(
+    window: &Window,
+    size: WindowSize,
+) -> tauri::Result<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+    let scale_factor = window.scale_factor()?;
+    let position = window.outer_position()?;
+    let mut target = LogicalSize::new(size.width, size.height).to_physical::<u32>(scale_factor);
+
+    let area = match window.current_monitor()? {
+        Some(monitor) => monitor::work_area(&monitor),
+        None => return Ok((position, target)),
+    };
+    target.width = target.width.min(area.width);
+    target.height = target.height.min(area.height);
+
+    let x = position
+        .x
+        .clamp(area.x, area.x + (area.width - target.width) as i32);
+    let y = position
+        .y
+        .clamp(area.y, area.y + (area.height - target.height) as i32);
+    Ok((PhysicalPosition::new(x, y), target))
+}
+
+fn interpolate(from: i32, to: i32, progress: f64) -> i32 {
+    from + ((to - from) as f64 * progress).round() as i32
+}
+
+// Resize and reposition the window smoothly on a background thread
+fn animate(
+    window: Window,
+    mode: WindowMode,
+    target: (PhysicalPosition<i32>, PhysicalSize<u32>),
+    generation: u64,
+) -> tauri::Result<()> {
+    let start_position = window.outer_position()?;
+    let start_size = window.inner_size()?;
+    let (end_position, end_size) = target;
+    let frame_delay = ANIMATION_DURATION / ANIMATION_FRAMES;
+
+    thread::spawn(move || {
+        let animation = window.state::<ModeAnimation>();
+        for frame in 1..=ANIMATION_FRAMES {
+            if animation.0.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            let progress = frame as f64 / ANIMATION_FRAMES as f64;
+            let position = PhysicalPosition::new(
+                interpolate(start_position.x, end_position.x, progress),
+                interpolate(start_position.y, end_position.y, progress),
+            );
+            let size = PhysicalSize::new(
+                interpolate(start_size.width as i32, end_size.width as i32, progress) as u32,
+                interpolate(start_size.height as i32, end_size.height as i32, progress) as u32,
+            );
+            if let Err(err) = window
+                .set_position(position)
+                .and_then(|_| window.set_size(size))
+            {
+                tracing::error!("Failed to animate window mode change: {}", err);
+                return;
+            }
+            thread::sleep(frame_delay);
+        }
+
+        if let Err(err) = apply_min_size(&window, mode) {
+            tracing::error!("Failed to update minimum window size: {}", err);
+        }
+        window
+            .emit_all("window-mode-changed", mode)
+            .unwrap_or_else(|err| tracing::error!("Failed to emit window mode change: {}", err));
+    });
+    Ok(())
+}
+
+// Animate the main window into a mode and persist it
+pub fn set(app: &AppHandle, mode: WindowMode) -> Result<(), String> {
+    let window = crate::main_window(app).ok_or("Main window not found")?;
+    let current = settings::current(app);
+    let size = match mode {
+        WindowMode::Compact => current.compact_size,
+        WindowMode::Expanded => current.expanded_size,
+    };
+
+    // Lift the minimum size first so the window can shrink into compact mode
+    if mode == WindowMode::Compact {
+        apply_min_size(&window, mode).map_err(|err| err.to_string())?;
+    }
+
+    let target = target_geometry(&window, size).map_err(|err| err.to_string())?;
+    let generation = app
+        .state::<ModeAnimation>()
+        .0
+        .fetch_add(1, Ordering::SeqCst)
+        + 1;
+    animate(window, mode, target, generation).map_err(|err| err.to_string())?;
+
+    settings::update(app, |settings| settings.window_mode = mode)
+}
+
+// Switch the main window between the compact pill and the expanded view
+#[tauri::command]
+pub fn set_window_mode(app: AppHandle, mode: WindowMode) -> Result<(), String> {
+    set(&app, mode)
+}
@@ -0,0 +1,175 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, Window};
+
+use crate::monitor::{self, Rect};
+
+// File under app_data_dir() holding the last main window geometry
+const WINDOW_STATE_FILE: &str = "window-state.json";
+
+// Quiet period after the last move/resize before geometry is written to disk
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+// Size used when the saved geometry is reset
+const DEFAULT_WIDTH: u32 = 600;
+const DEFAULT_HEIGHT: u32 = 400;
+
+// Outer position and inner size of the main window, in physical pixels
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+// Debounces geometry writes triggered by move/resize events on one long-lived worker
+pub struct GeometrySaver(Sender<()>);
+
+impl GeometrySaver {
+    // Start the worker, which saves the window geometry once a burst of events goes quiet and
+    // stops when the saver is dropped
+    pub fn new(window: &Window) -> Self {
+        let (sender, events) = mpsc::channel();
+        let window = window.clone();
+        thread::spawn(move || {
+            while events.recv().is_ok() {
+                loop {
+                    match events.recv_timeout(SAVE_DEBOUNCE) {
+                        Ok(()) => {}
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+                if let Err(err) = save_geometry(&window) {
+                    tracing::error!("Failed to save window geometry: {}", err);
+                }
+            }
+        });
+        GeometrySaver(sender)
+    }
+
+    // Note a move or resize; the geometry is saved after the debounce window
+    pub fn schedule(&self) {
+        let _ = self.0.send(());
+    }
+}
+
+fn state_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path_resolver()
+        .app_data_dir()
+        .map(|dir| dir.join(WINDOW_STATE_FILE))
+}
+
+// Read the saved geometry, if any
+pub fn load_geometry(app: &AppHandle) -> Option<WindowGeometry> {
+    let contents = fs::read_to_string(state_path(app)?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+// Write the current geometry of the window to disk
+fn save_geometry(window: &Window) -> Result<(), String> {
+    // Minimized and hidden windows report placeholder coordinates on some platforms
+    if window.is_minimized().unwrap_or(false) || !window.is_visible().unwrap_or(false) {
+        return Ok(());
+    }
+
+    // Keep the normal geometry so leaving maximized or fullscreen can return to it
+    if window.is_maximized().unwrap_or(false) || window.is_fullscreen().unwrap_or(false) {
+        return Ok(());
+    }
+
+    let position = window.outer_position().map_err(|err| err.to_string())?;
+    let size = window.inner_size().map_err(|err| err.to_string())?;
+    let geometry = WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+
+    let path = state_path(&window.app_handle()).ok_or("App data directory is unavailable")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(&geometry).map_err(|err| err.to_string())?;
+    // Through a temporary file, so a crash mid-write keeps the previous geometry
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", WINDOW_STATE_FILE, process::id()));
+    let result = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, &path));
+    if let Err(err) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(err.to_string());
+    }
+    Ok(())
+}
+
+// Center the window on the primary monitor
+fn center_on_primary(window: &Window) -> tauri::Result<()> {
+    let monitor = match window.primary_monitor()? {
+        Some(monitor) => monitor,
+        None => return window.center(),
+    };
+
+    let size = window.outer_size()?;
+    let origin = monitor.position();
+    let area = monitor.size();
+    let x = origin.x + (area.width as i32 - size.width as i32) / 2;
+    let y = origin.y + (area.height as i32 - size.height as i32) / 2;
+    window.set_position(PhysicalPosition::new(x, y))
+}
+
+// Apply the saved geometry to the window, centering it on the primary monitor when the
+// saved position is off-screen
+pub fn restore_geometry(window: &Window) {
+    let geometry = match load_geometry(&window.app_handle()) {
+        Some(geometry) => geometry,
+        None => return,
+    };
+
+    if let Err(err) = window.set_size(PhysicalSize::new(geometry.width, geometry.height)) {
+        tracing::error!("Failed to restore window size: {}", err);
+    }
+
+    let rect = Rect {
+        x: geometry.x,
+        y: geometry.y,
+        width: geometry.width,
+        height: geometry.height,
+    };
+    let result = if monitor::is_on_screen(window, &rect) {
+        window.set_position(PhysicalPosition::new(geometry.x, geometry.y))
+    } else {
+        center_on_primary(window)
+    };
+    if let Err(err) = result {
+        tracing::error!("Failed to restore window position: {}", err);
+    }
+}
+
+// Forget the saved geometry and move the window back to its default size and position
+#[tauri::command]
+pub fn reset_window_geometry(app: AppHandle) -> Result<(), String> {
+    if let Some(path) = state_path(&app) {
+        if path.exists() {
+            fs::remove_file(path).map_err(|err| err.to_string())?;
+        }
+    }
+
+    let window = crate::main_window(&app).ok_or("Main window not found")?;
+    window
+        .set_size(tauri::LogicalSize::new(DEFAULT_WIDTH, DEFAULT_HEIGHT))
+        .map_err(|err| err.to_string())?;
+    window.center().map_err(|err| err.to_string())
+}
@@ -0,0 +1,134 @@
+use tauri::{AppHandle, Manager, Window};
+
+use crate::settings;
+use crate::shortcut_registry;
+
+// Allowed webview zoom range and the step used by the keyboard shortcuts
+pub const MIN_ZOOM: f64 = 0.5;
+pub const MAX_ZOOM: f64 = 2.0;
+const ZOOM_STEP: f64 = 0.1;
+
+// Zoom accelerators, only registered while the main window has focus
+const ZOOM_SHORTCUTS: [(&str, ZoomAction); 3] = [
+    ("CmdOrCtrl+=", ZoomAction::In),
+    ("CmdOrCtrl+-", ZoomAction::Out),
+    ("CmdOrCtrl+0", ZoomAction::Reset),
+];
+
+#[derive(Debug, Clone, Copy)]
+enum ZoomAction {
+    In,
+    Out,
+    Reset,
+}
+
+impl ZoomAction {
+    fn id(self) -> &'static str {
+        match self {
+            ZoomAction::In => "zoom-in",
+            ZoomAction::Out => "zoom-out",
+            ZoomAction::Reset => "zoom-reset",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            ZoomAction::In => "Zoom in (while the window is focused)",
+            ZoomAction::Out => "Zoom out (while the window is focused)",
+            ZoomAction::Reset => "Reset zoom (while the window is focused)",
+        }
+    }
+}
+
+// Scale the page through the platform webview
+pub fn apply(window: &Window, factor: f64) -> tauri::Result<()> {
+    window.with_webview(move |webview| {
+        #[cfg(target_os = "windows")]
+        let result =
+            unsafe { webview.controller().SetZoomFactor(factor) }.map_err(|err| err.to_string());
+
+        #[cfg(target_os = "macos")]
+        let result: Result<(), String> = unsafe {
+            use cocoa::base::{BOOL, NO};
+            use objc::{msg_send, sel, sel_impl};
+
+            // setPageZoom: needs macOS 11
+            let wk_webview = webview.inner();
+            let supported: BOOL = msg_send![wk_webview, respondsToSelector: sel!(setPageZoom:)];
+            if supported == NO {
+                Err("Zoom needs macOS 11 or newer".to_string())
+            } else {
+                let _: () = msg_send![wk_webview, setPageZoom: factor];
+                Ok(())
+            }
+        };
+
+        #[cfg(target_os = "linux")]
+        let result: Result<(), String> = {
+            use webkit2gtk::WebViewExt;
+
+            webview.inner().set_zoom_level(factor);
+            Ok(())
+        };
+
+        if let Err(err) = result {
+            tracing::error!("Failed to set zoom: {}", err);
+        }
+    })
+}
+
+// Apply, persist and announce a new zoom factor
+pub fn set(app: &AppHandle, factor: f64) -> Result<f64, String> {
+    let factor = factor.clamp(MIN_ZOOM, MAX_ZOOM);
+    let window = crate::main_window(app).ok_or("Main window not found")?;
+    apply(&window, factor).map_err(|err| err.to_string())?;
+    settings::update(app, |settings| settings.zoom = factor)?;
+    app.emit_all("zoom-changed", factor)
+        .map_err(|err| err.to_string())?;
+    Ok(factor)
+}
+
+// Zoom in, out or back to 100% from the keyboard shortcuts
+fn run(app: &AppHandle, action: ZoomAction) {
+    let current = settings::current(app).zoom;
+    let factor = match action {
+        ZoomAction::In => current + ZOOM_STEP,
+        ZoomAction::Out => current - ZOOM_STEP,
+        ZoomAction::Reset => 1.0,
+    };
+    // Round so repeated steps land on whole percentages
+    if let Err(err) = set(app, (factor * 10.0).round() / 10.0) {
+        tracing::error!("Failed to change zoom: {}", err);
+    }
+}
+
+// Bind the zoom shortcuts while the main window is focused so they act like window shortcuts;
+// on blur they stay listed in the registry as inactive
+pub fn on_focus_changed(app: &AppHandle, focused: bool) {
+    for (accelerator, action) in ZOOM_SHORTCUTS {
+        if !focused {
+            shortcut_registry::deactivate(app, action.id());
+        } else if !shortcut_registry::activate(app, action.id()) {
+            let app_handle = app.clone();
+            let _ = shortcut_registry::register(
+                app,
+                action.id(),
+                accelerator,
+                action.description(),
+                move || run(&app_handle, action),
+            );
+        }
+    }
+}
+
+// Set the webview zoom factor, clamped to 0.5-2.0, and return the applied value
+#[tauri::command]
+pub fn set_zoom(app: AppHandle, factor: f64) -> Result<f64, String> {
+    set(&app, factor)
+}
+
+// Current webview zoom factor
+#[tauri::command]
+pub fn get_zoom(app: AppHandle) -> f64 {
+    settings::current(&app).zoom
+}
@@ -0,0 +1,14 @@
+import { listen } from '@tauri-apps/api/event';
+
+// Status text pushed by show_hud in src-tauri/src/hud.rs
+interface HudPayload {
+  text: string;
+}
+
+const status = document.getElementById('status');
+
+listen<HudPayload>('hud-update', (event) => {
+  if (status) {
+    status.textContent = event.payload.text;
+  }
+});
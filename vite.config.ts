@@ -1,5 +1,6 @@
 import { defineConfig } from 'vite';
 import react from '@vitejs/plugin-react';
+import { fileURLToPath } from 'node:url';
 
 // https://vitejs.dev/config/
 export default defineConfig(async () => ({
@@ -33,6 +34,11 @@ export default defineConfig(async () => ({
     
     // Rollup options
     rollupOptions: {
+      // Every page a Tauri window loads; the HUD window opens hud.html
+      input: {
+        main: fileURLToPath(new URL('./index.html', import.meta.url)),
+        hud: fileURLToPath(new URL('./hud.html', import.meta.url)),
+      },
       output: {
         manualChunks: {
           vendor: ['react', 'react-dom'],